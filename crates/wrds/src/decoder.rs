@@ -1,26 +1,58 @@
+#[cfg(feature = "unstable")]
+use crate::{
+    decoder::type_c_channels::TypeCChannels,
+    types::{FunctionHeader, TypeCGroup, RDS2_STREAMS, RFT_AID},
+};
 use crate::{
     decoder::{
+        alarm_detector::AlarmDetector,
+        char_voter::MAX_TEXT_VOTES,
+        dab_decoder::DabDecoder,
+        ews_decoder::{EmergencyWarningCallback, EwsDecoder},
+        in_house_decoder::{InHouseCallback, InHouseDecoder},
+        linkage_decoder::LinkageDecoder,
         mode_filter::ModeFilter,
+        oda_handler::{OdaGroup, OdaHandler, OdaHandlers, UnknownOdaCallback},
         oda_identifier::{OdaApplication, OdaIdentifier},
+        paging_decoder::PagingDecoder,
         ps_decoder::PsDecoder,
+        ptyn_decoder::PtynDecoder,
         rt_decoder::RtDecoder,
+        tdc_decoder::TdcDecoder,
+        tmc_decoder::TmcDecoder,
     },
     types::{
-        Block1, Block2, Block3, Block4, GroupType, GroupVariant, Message, Metadata,
-        ProgrammeIdentifier, RadioTextPlusContentType, RadioTextPlusTag,
+        Block1, Block2, Block3, Block4, Event, GroupType, GroupVariant, LinkedProgrammeList,
+        Message, Metadata, OdaAnnouncement, ProgrammeIdentifier, RadioTextPlusContentType,
+        RadioTextPlusTag, TmcKeyTable, TmcKeyTableSelector,
     },
-    ProgrammeType, TrafficProgram,
+    Error, ProgrammeType, TrafficAnnouncement, TrafficProgram,
 };
 
 use self::shared::Shared;
 
+mod alarm_detector;
 mod bitset;
+pub(crate) mod char_voter;
+mod dab_decoder;
+pub(crate) mod ews_decoder;
+pub(crate) mod in_house_decoder;
+mod linkage_decoder;
 mod mode_filter;
-mod oda_identifier;
+pub(crate) mod oda_handler;
+pub(crate) mod oda_identifier;
+mod paging_decoder;
 mod ps_decoder;
-mod rds_charset;
+mod ptyn_decoder;
+pub(crate) mod rds_charset;
 mod rt_decoder;
+mod segment_buffer;
 mod shared;
+pub(crate) mod tdc_decoder;
+pub(crate) mod tmc_decoder;
+pub(crate) mod tmc_store;
+#[cfg(feature = "unstable")]
+mod type_c_channels;
 
 const PI_FILTER_COUNT: usize = 6;
 const PI_FILTER_MIN: usize = 5;
@@ -31,50 +63,272 @@ const PTY_FILTER_MIN: usize = 5;
 const TP_FILTER_COUNT: usize = 6;
 const TP_FILTER_MIN: usize = 5;
 
+const TA_FILTER_COUNT: usize = 6;
+const TA_FILTER_MIN: usize = 5;
+
+/// Maximum number of pending events
+///
+/// The oldest event is dropped when the queue is full.
+pub const MAX_EVENTS: usize = 16;
+
 #[derive(Debug)]
-pub struct Decoder {
+pub struct Decoder<'a> {
     pi_filter: ModeFilter<ProgrammeIdentifier, PI_FILTER_COUNT>,
     pty_filter: ModeFilter<ProgrammeType, PTY_FILTER_COUNT>,
     tp_filter: ModeFilter<TrafficProgram, TP_FILTER_COUNT>,
+    ta_filter: ModeFilter<TrafficAnnouncement, TA_FILTER_COUNT>,
+    alarm_detector: AlarmDetector,
     ps_decoder: PsDecoder,
+    ptyn_decoder: PtynDecoder,
     rt_decoder: RtDecoder,
+    tmc_decoder: TmcDecoder,
+    ews_decoder: EwsDecoder<'a>,
+    paging_decoder: PagingDecoder,
+    tdc_decoder: TdcDecoder,
+    in_house_decoder: InHouseDecoder<'a>,
+    dab_decoder: DabDecoder,
+    linkage_decoder: LinkageDecoder,
+    #[cfg(feature = "unstable")]
+    type_c_channels: TypeCChannels,
+    /// Extended Country Code (ECC) from slow labelling variant 0
+    ecc: Option<u8>,
     oda_identifier: OdaIdentifier,
+    oda_handlers: OdaHandlers<'a>,
+    events: heapless::Deque<Event, MAX_EVENTS>,
 }
 
-impl Decoder {
+impl<'a> Decoder<'a> {
     /// Create new RDS decoder.
     pub fn new() -> Self {
         Decoder {
             pi_filter: ModeFilter::new(PI_FILTER_MIN).unwrap(),
             pty_filter: ModeFilter::new(PTY_FILTER_MIN).unwrap(),
             tp_filter: ModeFilter::new(TP_FILTER_MIN).unwrap(),
+            ta_filter: ModeFilter::new(TA_FILTER_MIN).unwrap(),
+            alarm_detector: AlarmDetector::new(),
             ps_decoder: PsDecoder::new(),
+            ptyn_decoder: PtynDecoder::new(),
             rt_decoder: RtDecoder::new(),
+            tmc_decoder: TmcDecoder::new(),
+            ews_decoder: EwsDecoder::new(),
+            paging_decoder: PagingDecoder::new(),
+            tdc_decoder: TdcDecoder::new(),
+            in_house_decoder: InHouseDecoder::new(),
+            dab_decoder: DabDecoder::new(),
+            linkage_decoder: LinkageDecoder::new(),
+            #[cfg(feature = "unstable")]
+            type_c_channels: TypeCChannels::new(),
+            ecc: None,
             oda_identifier: oda_identifier::OdaIdentifier::new(),
+            oda_handlers: OdaHandlers::new(),
+            events: heapless::Deque::new(),
+        }
+    }
+
+    /// Register a handler for an Open Data Application (ODA).
+    ///
+    /// The handler will receive the group 3A message bits and every group
+    /// of the type and variant assigned to its AID.
+    ///
+    /// # Errors
+    /// Returns an error if the maximum number of handlers is already registered.
+    pub fn register_oda_handler(&mut self, handler: &'a mut dyn OdaHandler) -> Result<(), Error> {
+        self.oda_handlers
+            .register(handler)
+            .map_err(|_| Error::MaxOdaHandlersExceeded)
+    }
+
+    /// Set the callback for groups carrying an ODA that has no registered handler.
+    ///
+    /// The callback receives the announced AID and the raw blocks of the group.
+    /// Applications decoded by this crate, such as RT+, TMC and DAB cross-referencing,
+    /// are not passed to the callback.
+    pub fn set_unknown_oda_callback(&mut self, callback: UnknownOdaCallback<'a>) {
+        self.oda_handlers.set_unknown_callback(callback);
+    }
+
+    /// Set the callback for Emergency Warning System (EWS) messages from group 9A.
+    ///
+    /// The callback receives each new message alongside
+    /// [`Event::EmergencyWarning`](crate::Event::EmergencyWarning), so that
+    /// country-specific EWS formats can be decoded as soon as they arrive.
+    pub fn set_emergency_warning_callback(&mut self, callback: EmergencyWarningCallback<'a>) {
+        self.ews_decoder.set_callback(callback);
+    }
+
+    /// Set the callback for in-house data from groups 6A and 6B.
+    ///
+    /// The callback then receives every payload instead of [`Event::InHouse`](crate::Event::InHouse),
+    /// so that frequent group 6 data neither gets lost nor evicts other pending events.
+    /// Group 6 is only decoded as in-house data while no ODA is assigned to it.
+    pub fn set_in_house_callback(&mut self, callback: InHouseCallback<'a>) {
+        self.in_house_decoder.set_callback(callback);
+    }
+
+    /// Set the maximum number of Open Data Applications (ODA) tracked per station.
+    ///
+    /// # Errors
+    /// Returns an error if the capacity exceeds [`crate::MAX_ODA_APPS`].
+    pub fn set_oda_capacity(&mut self, capacity: usize) -> Result<(), Error> {
+        self.oda_identifier
+            .set_capacity(capacity)
+            .map_err(|_| Error::InvalidInput {
+                field: "ODA capacity must not exceed MAX_ODA_APPS",
+                value: capacity.try_into().unwrap_or(u16::MAX),
+            })
+    }
+
+    /// Enable per-character voting for the PS and the RadioText, or disable it if `None`.
+    ///
+    /// Each character is then only confirmed after `min_votes` agreeing receptions,
+    /// and a corrupted group no longer replaces or resets a confirmed text. This
+    /// gives a stable PS under weak signals, at the cost of a slower first display.
+    ///
+    /// # Errors
+    /// Returns an error if `min_votes` is 0 or exceeds [`crate::MAX_TEXT_VOTES`].
+    pub fn set_text_voting(&mut self, min_votes: Option<u8>) -> Result<(), Error> {
+        if let Some(votes) = min_votes.filter(|votes| !(1..=MAX_TEXT_VOTES).contains(votes)) {
+            return Err(Error::InvalidInput {
+                field: "Text voting must require between 1 and MAX_TEXT_VOTES votes",
+                value: votes.into(),
+            });
+        }
+        self.ps_decoder.set_voting(min_votes);
+        self.rt_decoder.set_voting(min_votes);
+        Ok(())
+    }
+
+    /// Set the number of groups after which an Open Data Application (ODA)
+    /// expires if group 3A does not announce it again.
+    pub fn set_oda_timeout(&mut self, groups: u32) {
+        self.oda_identifier.set_timeout(groups);
+    }
+
+    /// Set the key table used to decrypt the location codes of encrypted TMC services.
+    ///
+    /// The encryption administration group of the service selects the table and
+    /// the key within it. Key tables are kept when the decoder is reset.
+    pub fn set_tmc_key_table(&mut self, selector: TmcKeyTableSelector, table: TmcKeyTable) {
+        self.tmc_decoder.set_key_table(selector, table);
+    }
+
+    /// Set the number of bytes buffered for each Transparent Data Channel (TDC).
+    ///
+    /// # Errors
+    /// Returns an error if the size exceeds [`crate::MAX_TDC_BUFFER_SIZE`].
+    pub fn set_tdc_buffer_size(&mut self, size: usize) -> Result<(), Error> {
+        self.tdc_decoder
+            .set_buffer_size(size)
+            .map_err(|_| Error::InvalidInput {
+                field: "TDC buffer size must not exceed MAX_TDC_BUFFER_SIZE",
+                value: size.try_into().unwrap_or(u16::MAX),
+            })
+    }
+
+    /// Read the bytes received on a Transparent Data Channel (TDC) into `buffer`.
+    ///
+    /// Group 5 is only decoded as TDC while no ODA is assigned to it. Returns the
+    /// number of bytes read, which is 0 for channels outside of [`crate::TDC_CHANNELS`].
+    pub fn read_tdc(&mut self, channel: u8, buffer: &mut [u8]) -> usize {
+        self.tdc_decoder.read(channel.into(), buffer)
+    }
+
+    /// Returns the number of bytes waiting to be read on a Transparent Data Channel (TDC)
+    pub fn tdc_available(&self, channel: u8) -> usize {
+        self.tdc_decoder.available(channel.into())
+    }
+
+    /// Returns the PIs of the other networks currently carrying the same programme
+    /// as the tuned station, according to the linkage information of group 14A.
+    ///
+    /// The list is empty until the PI of the tuned station is known and while
+    /// its programme is not linked.
+    pub fn linked_programmes(&self) -> LinkedProgrammeList {
+        match self.pi_filter.mode() {
+            Some(pi) => self.linkage_decoder.linked_programmes(pi),
+            None => LinkedProgrammeList::new(),
         }
     }
 
     /// Decode the RDS message and return the current state of the RDS metadata.
+    ///
+    /// Groups of the additional RDS2 streams do not affect the PI or any other
+    /// metadata. With the `unstable` feature, their type C groups are passed to the
+    /// registered ODA handlers, otherwise they are dropped.
     pub fn decode(&mut self, blocks: &Message) -> Metadata {
+        if blocks.stream != 0 {
+            #[cfg(feature = "unstable")]
+            self.decode_rds2_stream(blocks);
+            return self.metadata();
+        }
+
+        self.oda_identifier.tick();
         self.decode_block1(&blocks.block1);
 
         // Return immediately if Block 2 is not provided because it determines
         // how to decode Block 3 and 4.
         if let Some(block2) = blocks.block2 {
-            self.decode_blocks234(&block2, &blocks.block3, &blocks.block4);
+            self.decode_blocks234(&blocks.block1, &block2, &blocks.block3, &blocks.block4);
         }
 
         self.metadata()
     }
 
+    /// Returns the oldest pending event, if any.
+    ///
+    /// Events are queued while decoding and should be polled after each call to
+    /// [`Decoder::decode`]. The oldest event is dropped once [`MAX_EVENTS`] are pending.
+    pub fn poll_event(&mut self) -> Option<Event> {
+        self.events.pop_front()
+    }
+
     /// Reset Decoder's state to default.
     /// This method should be called after tuning to a different station.
     pub fn reset(&mut self) {
         self.pi_filter.reset();
         self.pty_filter.reset();
         self.tp_filter.reset();
+        self.ta_filter.reset();
+        self.alarm_detector.reset();
         self.ps_decoder.reset();
+        self.ptyn_decoder.reset();
         self.rt_decoder.reset();
+        self.tmc_decoder.reset();
+        self.ews_decoder.reset();
+        self.paging_decoder.reset();
+        self.tdc_decoder.reset();
+        self.dab_decoder.reset();
+        self.linkage_decoder.reset();
+        #[cfg(feature = "unstable")]
+        self.type_c_channels.reset();
+        self.ecc = None;
+        self.oda_identifier.reset();
+        self.events.clear();
+    }
+
+    /// Decode a complete type C group of the RDS2 streams 1 to 3.
+    #[cfg(feature = "unstable")]
+    fn decode_rds2_stream(&mut self, blocks: &Message) {
+        if blocks.stream >= RDS2_STREAMS {
+            return;
+        }
+        let (Some(block1), Some(block2), Some(block3), Some(block4)) =
+            (blocks.block1, blocks.block2, blocks.block3, blocks.block4)
+        else {
+            return;
+        };
+        let group = TypeCGroup::new(blocks.stream, [block1.0, block2.0, block3.0, block4.0]);
+        if self.type_c_channels.push_group(&group) {
+            return;
+        }
+        let aid = match group.function_header {
+            FunctionHeader::Oda { channel } => self.type_c_channels.aid(channel),
+            FunctionHeader::Rft { .. } => Some(RFT_AID),
+            FunctionHeader::Reserved(_) => None,
+        };
+        if let Some(aid) = aid {
+            self.oda_handlers.decode_type_c_group(aid, &group);
+        }
     }
 
     /// Decode Block 1 as the Programme Identifier (PI) if provided.
@@ -89,6 +343,7 @@ impl Decoder {
     /// Block 2 must be provided because it determines how to decode Blocks 3 and 4.
     fn decode_blocks234(
         &mut self,
+        block1: &Option<Block1>,
         block2: &Block2,
         maybe_block3: &Option<Block3>,
         maybe_block4: &Option<Block4>,
@@ -98,17 +353,42 @@ impl Decoder {
         self.handle_group_variant_b_pi(&shared, maybe_block3);
         self.pty_filter.push(shared.pty);
         self.tp_filter.push(shared.tp);
+        if self.alarm_detector.push(shared.pty) {
+            self.push_event(match self.alarm_detector.is_active() {
+                true => Event::AlarmStarted,
+                false => Event::AlarmEnded,
+            });
+        }
 
         const GROUP_TYPE0: GroupType = GroupType(0);
+        const GROUP_TYPE1: GroupType = GroupType(1);
         const GROUP_TYPE2: GroupType = GroupType(2);
         const GROUP_TYPE3: GroupType = GroupType(3);
+        const GROUP_TYPE5: GroupType = GroupType(5);
+        const GROUP_TYPE6: GroupType = GroupType(6);
+        #[cfg(feature = "unstable")]
+        const GROUP_TYPE7: GroupType = GroupType(7);
+        const GROUP_TYPE8: GroupType = GroupType(8);
+        const GROUP_TYPE9: GroupType = GroupType(9);
+        const GROUP_TYPE10: GroupType = GroupType(10);
+        #[cfg(feature = "unstable")]
+        const GROUP_TYPE13: GroupType = GroupType(13);
+        const GROUP_TYPE14: GroupType = GroupType(14);
+        const GROUP_TYPE15: GroupType = GroupType(15);
 
         match (shared.gt, shared.gv) {
             (GROUP_TYPE0, _) => {
+                self.handle_traffic_announcement(block2);
                 if let Some(block4) = maybe_block4 {
                     self.handle_ps_name(block2, block4);
                 }
             }
+            (GROUP_TYPE1, GroupVariant::A) => {
+                self.paging_decoder.push_paging_codes(block2);
+                if let Some(block3) = maybe_block3 {
+                    self.handle_slow_labelling(block3);
+                }
+            }
             (GROUP_TYPE2, _) => self.handle_radio_text(&shared, block2, maybe_block3, maybe_block4),
             (GROUP_TYPE3, GroupVariant::A) => {
                 self.handle_oda_identification(block2, maybe_block3, maybe_block4)
@@ -118,8 +398,39 @@ impl Decoder {
                     .oda_identifier
                     .get_app(gt, gv)
                     .expect("The app should exist in ODA Identifier");
-                self.handle_oda(app, block2, maybe_block3, maybe_block4);
+                let group = OdaGroup {
+                    group_type: gt,
+                    group_variant: gv,
+                    block2: *block2,
+                    block3: *maybe_block3,
+                    block4: *maybe_block4,
+                };
+                self.handle_oda(app, &group);
+            }
+            (GROUP_TYPE5, gv) => self.handle_tdc(gv, block2, maybe_block3, maybe_block4),
+            (GROUP_TYPE6, gv) => {
+                self.handle_in_house(gv, block1, block2, maybe_block3, maybe_block4)
             }
+            #[cfg(feature = "unstable")]
+            (GROUP_TYPE7, GroupVariant::A) => {
+                self.handle_paging(block2, maybe_block3, maybe_block4)
+            }
+            // Group 8A was allocated to TMC before ODA existed
+            (GROUP_TYPE8, GroupVariant::A) => self.handle_tmc(block2, maybe_block3, maybe_block4),
+            (GROUP_TYPE9, GroupVariant::A) => self.handle_ews(block2, maybe_block3, maybe_block4),
+            (GROUP_TYPE10, GroupVariant::A) => self.handle_ptyn(block2, maybe_block3, maybe_block4),
+            #[cfg(feature = "unstable")]
+            (GROUP_TYPE13, GroupVariant::A) => {
+                if let Some(block3) = maybe_block3 {
+                    self.paging_decoder.push_enhanced_group(block2, block3);
+                }
+            }
+            (GROUP_TYPE14, GroupVariant::A) => {
+                if let Some((block3, block4)) = maybe_block3.zip(*maybe_block4) {
+                    self.linkage_decoder.push_group(block2, &block3, &block4);
+                }
+            }
+            (GROUP_TYPE15, GroupVariant::B) => self.handle_traffic_announcement(block2),
             _ => {}
         }
     }
@@ -132,6 +443,37 @@ impl Decoder {
         }
     }
 
+    fn handle_traffic_announcement(&mut self, block2: &Block2) {
+        const TA_BITMASK: u16 = 0x10;
+        self.ta_filter
+            .push(TrafficAnnouncement(block2.0 & TA_BITMASK != 0));
+    }
+
+    /// Decode the slow labelling codes in Block 3 of group 1A
+    fn handle_slow_labelling(&mut self, block3: &Block3) {
+        const LA_BITMASK: u16 = 0x8000;
+        const VARIANT_SHIFT: usize = 12;
+        const VARIANT_BITMASK: u16 = 0x7;
+        const DATA_BITMASK: u16 = 0xFFF;
+        const ECC_VARIANT: u16 = 0;
+        const PAGING_IDENTIFICATION_VARIANT: u16 = 2;
+        const EWS_CHANNEL_VARIANT: u16 = 7;
+
+        self.linkage_decoder
+            .push_linkage_actuator(block3.0 & LA_BITMASK != 0);
+        let data = block3.0 & DATA_BITMASK;
+        match (block3.0 >> VARIANT_SHIFT) & VARIANT_BITMASK {
+            ECC_VARIANT => {
+                let [operator_code, ecc] = data.to_be_bytes();
+                self.paging_decoder.push_operator_code(operator_code);
+                self.ecc = Some(ecc);
+            }
+            PAGING_IDENTIFICATION_VARIANT => self.paging_decoder.push_paging_identification(data),
+            EWS_CHANNEL_VARIANT => self.ews_decoder.push_channel_id(data),
+            _ => {}
+        }
+    }
+
     fn handle_ps_name(&mut self, block2: &Block2, block4: &Block4) {
         const PS_IDX_BITMASK: u16 = 0b11;
         let idx = block2.0 & PS_IDX_BITMASK;
@@ -141,6 +483,24 @@ impl Decoder {
             .expect("PS segment index should always be valid after bit-masking");
     }
 
+    fn handle_ptyn(
+        &mut self,
+        block2: &Block2,
+        maybe_block3: &Option<Block3>,
+        maybe_block4: &Option<Block4>,
+    ) {
+        const PTYN_IDX_BITMASK: u16 = 0b1;
+        const TEXT_AB_BITMASK: u16 = 0x10;
+        if let (Some(block3), Some(block4)) = (maybe_block3, maybe_block4) {
+            let index = usize::from(block2.0 & PTYN_IDX_BITMASK);
+            let text_ab = (block2.0 & TEXT_AB_BITMASK) > 0;
+            let [char1, char2] = block3.0.to_be_bytes();
+            let [char3, char4] = block4.0.to_be_bytes();
+            self.ptyn_decoder
+                .push_segment(index, [char1, char2, char3, char4], text_ab);
+        }
+    }
+
     fn handle_radio_text(
         &mut self,
         shared: &Shared,
@@ -190,14 +550,11 @@ impl Decoder {
         maybe_block3: &Option<Block3>,
         maybe_block4: &Option<Block4>,
     ) {
-        let (_block3, block4) = match maybe_block3.zip(*maybe_block4) {
+        let (block3, block4) = match maybe_block3.zip(*maybe_block4) {
             Some(v) => v,
             None => return,
         };
-        let oda_app = match OdaApplication::try_from(block4.0) {
-            Ok(val) => val,
-            Err(_) => return,
-        };
+        let oda_app = OdaApplication::from_aid(block4.0);
         let oda_variant = {
             const ODA_VARIANT_BITMASK: u16 = 0x1;
             GroupVariant::from(block2.0 & ODA_VARIANT_BITMASK == 1)
@@ -215,19 +572,47 @@ impl Decoder {
                 .oda_identifier
                 .add_new_app(oda_group, oda_variant, oda_app);
         }
+        self.oda_identifier.announce(OdaAnnouncement::new(
+            oda_app.aid(),
+            oda_group,
+            oda_variant,
+            block3.0,
+        ));
+        if let OdaApplication::Tmc(_) = oda_app {
+            self.tmc_decoder.push_system_information(block3.0);
+        }
+        self.oda_handlers
+            .identify(oda_app.aid(), oda_group, oda_variant, block3.0);
     }
 
-    fn handle_oda(
+    fn handle_oda(&mut self, app: OdaApplication, group: &OdaGroup) {
+        match app {
+            OdaApplication::RtPlus => {
+                self.handle_rt_plus(&group.block2, &group.block3, &group.block4)
+            }
+            OdaApplication::Tmc(_) => self.handle_tmc(&group.block2, &group.block3, &group.block4),
+            OdaApplication::Dab => self.handle_dab(group),
+            OdaApplication::Other(_) => {}
+        }
+        self.oda_handlers.decode_group(app, group);
+    }
+
+    fn handle_dab(&mut self, group: &OdaGroup) {
+        // Block 3 carries the PI instead of data in type B groups
+        if group.group_variant != GroupVariant::A {
+            return;
+        }
+        if let Some((block3, block4)) = group.block3.zip(group.block4) {
+            self.dab_decoder.push_group(&group.block2, &block3, &block4);
+        }
+    }
+
+    fn handle_rt_plus(
         &mut self,
-        app: OdaApplication,
         block2: &Block2,
         maybe_block3: &Option<Block3>,
         maybe_block4: &Option<Block4>,
     ) {
-        if app != OdaApplication::RtPlus {
-            return;
-        }
-
         let (block3, block4) = match maybe_block3.zip(*maybe_block4) {
             Some(v) => v,
             None => return,
@@ -268,13 +653,135 @@ impl Decoder {
         self.rt_decoder.push_rt_plus_tags(tag1, tag2);
     }
 
+    fn handle_tmc(
+        &mut self,
+        block2: &Block2,
+        maybe_block3: &Option<Block3>,
+        maybe_block4: &Option<Block4>,
+    ) {
+        let (block3, block4) = match maybe_block3.zip(*maybe_block4) {
+            Some(v) => v,
+            None => return,
+        };
+        if let Some(message) = self.tmc_decoder.push_group(block2, &block3, &block4) {
+            self.push_event(Event::TmcMessage(message));
+        }
+    }
+
+    fn handle_ews(
+        &mut self,
+        block2: &Block2,
+        maybe_block3: &Option<Block3>,
+        maybe_block4: &Option<Block4>,
+    ) {
+        let (block3, block4) = match maybe_block3.zip(*maybe_block4) {
+            Some(v) => v,
+            None => return,
+        };
+        if let Some(warning) = self.ews_decoder.push_group(block2, &block3, &block4) {
+            self.push_event(Event::EmergencyWarning(warning));
+        }
+    }
+
+    fn handle_tdc(
+        &mut self,
+        gv: GroupVariant,
+        block2: &Block2,
+        maybe_block3: &Option<Block3>,
+        maybe_block4: &Option<Block4>,
+    ) {
+        // Block 3 carries the PI instead of data in group 5B
+        let block3 = match (gv, maybe_block3) {
+            (GroupVariant::A, Some(block3)) => Some(block3),
+            (GroupVariant::A, None) => return,
+            (GroupVariant::B, _) => None,
+        };
+        if let Some(block4) = maybe_block4 {
+            self.tdc_decoder.push_group(block2, block3, block4);
+        }
+    }
+
+    fn handle_in_house(
+        &mut self,
+        gv: GroupVariant,
+        block1: &Option<Block1>,
+        block2: &Block2,
+        maybe_block3: &Option<Block3>,
+        maybe_block4: &Option<Block4>,
+    ) {
+        // Block 3 carries the PI instead of data in group 6B
+        let block3 = match (gv, maybe_block3) {
+            (GroupVariant::A, Some(block3)) => Some(block3),
+            (GroupVariant::A, None) => return,
+            (GroupVariant::B, _) => None,
+        };
+        let Some(block4) = maybe_block4 else {
+            return;
+        };
+        let pi = match block1 {
+            Some(block1) => Some(ProgrammeIdentifier(block1.0)),
+            None => self.pi_filter.mode(),
+        };
+        if let Some(data) = self.in_house_decoder.push_group(pi, block2, block3, block4) {
+            self.push_event(Event::InHouse(data));
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    fn handle_paging(
+        &mut self,
+        block2: &Block2,
+        maybe_block3: &Option<Block3>,
+        maybe_block4: &Option<Block4>,
+    ) {
+        let (block3, block4) = match maybe_block3.zip(*maybe_block4) {
+            Some(v) => v,
+            None => return,
+        };
+        if let Some(mut message) = self.paging_decoder.push_group(block2, &block3, &block4) {
+            message.network = self.paging_network();
+            self.push_event(Event::Paging(message));
+        }
+    }
+
+    /// Returns the paging network once the ECC, PI and paging identification are known
+    #[cfg(feature = "unstable")]
+    fn paging_network(&self) -> Option<crate::types::PagingNetwork> {
+        const COUNTRY_CODE_SHIFT: usize = 12;
+
+        let info = self.paging_decoder.info()?;
+        Some(crate::types::PagingNetwork {
+            ecc: self.ecc?,
+            country_code: (self.pi_filter.mode()?.0 >> COUNTRY_CODE_SHIFT) as u8,
+            operator_code: info.operator_code?,
+            area_code: info.area_code?,
+        })
+    }
+
+    fn push_event(&mut self, event: Event) {
+        if self.events.is_full() {
+            self.events.pop_front();
+        }
+        let _ = self.events.push_back(event);
+    }
+
     fn metadata(&self) -> Metadata {
         Metadata {
             pi: self.pi_filter.mode(),
             pty: self.pty_filter.mode(),
             tp: self.tp_filter.mode(),
+            ta: self.ta_filter.mode(),
+            alarm: self.alarm_detector.is_active(),
             ps: self.ps_decoder.confirmed(),
             rt: self.rt_decoder.confirmed(),
+            oda: self.oda_identifier.announced(),
+            tmc: self.tmc_decoder.service().cloned(),
+            ecc: self.ecc,
+            paging: self.paging_decoder.info(),
+            linkage_actuator: self.linkage_decoder.linkage_actuator().unwrap_or(false),
+            linkage: self.linkage_decoder.networks().clone(),
+            dab: self.dab_decoder.linkages().clone(),
+            ptyn: self.ptyn_decoder.confirmed(),
         }
     }
 
@@ -291,7 +798,7 @@ impl Decoder {
     }
 }
 
-impl Default for Decoder {
+impl Default for Decoder<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -0,0 +1,93 @@
+use crate::types::ProgrammeType;
+
+/// Programme type signalling an alarm or alarm test
+const PTY_ALARM: ProgrammeType = ProgrammeType(31);
+
+/// Number of consecutive groups with PTY 31 that start an alarm
+const ALARM_START_COUNT: u8 = 2;
+
+/// Number of consecutive groups without PTY 31 that end an alarm
+const ALARM_END_COUNT: u8 = 4;
+
+/// Detects alarms (PTY 31) from the unfiltered PTY of each group.
+///
+/// Alarms start after fewer groups than the PTY filter requires so that they
+/// are announced without delay, while single corrupted groups are still ignored.
+#[derive(Debug)]
+pub struct AlarmDetector {
+    active: bool,
+    /// Number of consecutive groups contradicting the current state
+    count: u8,
+}
+
+impl AlarmDetector {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            count: 0,
+        }
+    }
+
+    /// Push the PTY of a group.
+    ///
+    /// Returns true if the alarm started or ended with this group.
+    pub fn push(&mut self, pty: ProgrammeType) -> bool {
+        if (pty == PTY_ALARM) == self.active {
+            self.count = 0;
+            return false;
+        }
+
+        self.count += 1;
+        let required = match self.active {
+            true => ALARM_END_COUNT,
+            false => ALARM_START_COUNT,
+        };
+        if self.count < required {
+            return false;
+        }
+        self.active = !self.active;
+        self.count = 0;
+        true
+    }
+
+    /// Returns true while an alarm is active
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn reset(&mut self) {
+        self.active = false;
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PTY_NEWS: ProgrammeType = ProgrammeType(1);
+
+    #[test]
+    fn test_start_and_end() {
+        let mut detector = AlarmDetector::new();
+        assert!(!detector.push(PTY_ALARM));
+        assert!(detector.push(PTY_ALARM));
+        assert!(detector.is_active());
+
+        for _ in 0..ALARM_END_COUNT - 1 {
+            assert!(!detector.push(PTY_NEWS));
+        }
+        assert!(detector.is_active());
+        assert!(detector.push(PTY_NEWS));
+        assert!(!detector.is_active());
+    }
+
+    #[test]
+    fn test_single_group_is_ignored() {
+        let mut detector = AlarmDetector::new();
+        assert!(!detector.push(PTY_ALARM));
+        assert!(!detector.push(PTY_NEWS));
+        assert!(!detector.push(PTY_ALARM));
+        assert!(!detector.is_active());
+    }
+}
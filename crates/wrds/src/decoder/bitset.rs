@@ -41,8 +41,10 @@ impl<const N: usize> Bitset<N> {
         all_set == val
     }
 
-    /// Returns the underlying value of the bitset
-    pub fn value(&self) -> u16 {
-        self.underlying
+    /// Returns true if the first `count` bits are set
+    pub fn first_set(&self, count: usize) -> bool {
+        let val: u32 = self.underlying.into();
+        let first = (1 << count.min(MAX_POSITION)) - 1;
+        val & first == first
     }
 }
@@ -0,0 +1,115 @@
+/// Maximum number of candidate characters kept for each position
+const MAX_CANDIDATES: usize = 4;
+
+/// Maximum number of agreeing receptions that can be required per PS or RT character
+pub const MAX_TEXT_VOTES: u8 = 16;
+
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    byte: u8,
+    count: u8,
+}
+
+/// [`CharVoter`] keeps candidate counts for each of the `N` character positions of a text.
+///
+/// Receiving a character votes for it and against the other candidates of the
+/// position, so that a character is only confirmed after `min_votes` agreeing
+/// receptions and a single error cannot replace it. Counts saturate at twice
+/// `min_votes` to follow genuine changes of the text.
+#[derive(Debug, Clone)]
+pub struct CharVoter<const N: usize> {
+    candidates: [[Option<Candidate>; MAX_CANDIDATES]; N],
+    min_votes: u8,
+}
+
+impl<const N: usize> CharVoter<N> {
+    /// Create new instance of [`CharVoter`], with `min_votes` clamped to
+    /// 1..=[`MAX_TEXT_VOTES`]
+    pub fn new(min_votes: u8) -> Self {
+        Self {
+            candidates: [[None; MAX_CANDIDATES]; N],
+            min_votes: min_votes.clamp(1, MAX_TEXT_VOTES),
+        }
+    }
+
+    /// Vote for the character at the position, returning the confirmed character
+    pub fn push(&mut self, position: usize, byte: u8) -> Option<u8> {
+        let max_count = self.min_votes * 2;
+        let slots = self.candidates.get_mut(position)?;
+        let mut found = false;
+        for slot in slots.iter_mut() {
+            match slot {
+                Some(candidate) if candidate.byte == byte => {
+                    candidate.count = (candidate.count + 1).min(max_count);
+                    found = true;
+                }
+                Some(candidate) => {
+                    candidate.count -= 1;
+                    if candidate.count == 0 {
+                        *slot = None;
+                    }
+                }
+                None => {}
+            }
+        }
+        if !found {
+            // Replace a free slot or the least voted candidate
+            let slot = slots
+                .iter_mut()
+                .min_by_key(|slot| slot.map_or(0, |candidate| candidate.count))
+                .expect("There should always be candidate slots");
+            *slot = Some(Candidate { byte, count: 1 });
+        }
+        self.winner(position)
+    }
+
+    /// Returns the most voted character at the position if it has at least
+    /// `min_votes` and no other candidate has as many
+    pub fn winner(&self, position: usize) -> Option<u8> {
+        let candidates = self.candidates.get(position)?.iter().flatten();
+        let best = candidates.clone().max_by_key(|candidate| candidate.count)?;
+        let ties = candidates
+            .filter(|candidate| candidate.count == best.count)
+            .count();
+        (best.count >= self.min_votes && ties == 1).then_some(best.byte)
+    }
+
+    pub fn reset(&mut self) {
+        self.candidates = [[None; MAX_CANDIDATES]; N];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirms_after_agreement() {
+        let mut voter = CharVoter::<2>::new(3);
+        assert_eq!(voter.push(0, b'A'), None);
+        assert_eq!(voter.push(0, b'A'), None);
+        assert_eq!(voter.push(0, b'A'), Some(b'A'));
+        assert_eq!(voter.push(0, b'A'), Some(b'A'));
+        assert_eq!(voter.winner(1), None);
+        assert_eq!(voter.winner(2), None);
+
+        // A single error does not replace the character
+        assert_eq!(voter.push(0, b'B'), Some(b'A'));
+        assert_eq!(voter.push(0, b'A'), Some(b'A'));
+
+        voter.reset();
+        assert_eq!(voter.winner(0), None);
+    }
+
+    #[test]
+    fn test_follows_changes() {
+        let mut voter = CharVoter::<1>::new(2);
+        for _ in 0..10 {
+            voter.push(0, b'A');
+        }
+        assert_eq!(voter.push(0, b'B'), Some(b'A'));
+        // Tied candidates are not confirmed
+        assert_eq!(voter.push(0, b'B'), None);
+        assert_eq!(voter.push(0, b'B'), Some(b'B'));
+    }
+}
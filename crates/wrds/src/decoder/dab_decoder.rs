@@ -0,0 +1,126 @@
+//! Decoder for DAB cross-referencing (AID 0x0093, ETSI EN 301 700).
+//!
+//! The 5 bits at the end of Block 2 start with the E/S flag. Ensemble table
+//! groups (E/S = 0) carry the mode and the 2 most significant bits of the
+//! frequency in Block 2, the 16 remaining frequency bits in Block 3 and the EId
+//! in Block 4. The frequency is a multiple of 16 kHz.
+//!
+//! Service table groups (E/S = 1) carry a 4-bit variant code in Block 2 and the
+//! SId in Block 4. Variant 0 links the service to the EId in Block 3; the other
+//! variants are not decoded.
+
+use crate::types::{Block2, Block3, Block4, DabLinkage, DabLinkageList, DabMode};
+
+const SERVICE_TABLE_BITMASK: u16 = 0x10;
+const MODE_SHIFT: usize = 2;
+const MODE_BITMASK: u16 = 0b11;
+const FREQUENCY_MSB_BITMASK: u16 = 0b11;
+const VARIANT_BITMASK: u16 = 0xF;
+/// Service table variant linking the service to an ensemble
+const SERVICE_ENSEMBLE_VARIANT: u16 = 0;
+
+/// Frequency step of the ensemble table in kHz
+const FREQUENCY_STEP_KHZ: u32 = 16;
+
+#[derive(Debug)]
+pub struct DabDecoder {
+    linkages: DabLinkageList,
+}
+
+impl DabDecoder {
+    pub fn new() -> Self {
+        Self {
+            linkages: DabLinkageList::new(),
+        }
+    }
+
+    /// Push a group type A of the DAB cross-referencing ODA
+    pub fn push_group(&mut self, block2: &Block2, block3: &Block3, block4: &Block4) {
+        if block2.0 & SERVICE_TABLE_BITMASK == 0 {
+            let frequency =
+                (u32::from(block2.0 & FREQUENCY_MSB_BITMASK) << 16) | u32::from(block3.0);
+            let linkage = self.linkage_mut(block4.0);
+            linkage.frequency = Some(frequency * FREQUENCY_STEP_KHZ);
+            linkage.mode = DabMode::from(((block2.0 >> MODE_SHIFT) & MODE_BITMASK) as u8);
+            return;
+        }
+
+        if block2.0 & VARIANT_BITMASK != SERVICE_ENSEMBLE_VARIANT {
+            return;
+        }
+        let (ensemble_id, service_id) = (block3.0, block4.0);
+        let service_ids = &mut self.linkage_mut(ensemble_id).service_ids;
+        if service_ids.contains(&service_id) {
+            return;
+        }
+        if service_ids.is_full() {
+            service_ids.remove(0);
+        }
+        let _ = service_ids.push(service_id);
+    }
+
+    /// Returns the DAB ensembles received so far
+    pub fn linkages(&self) -> &DabLinkageList {
+        &self.linkages
+    }
+
+    pub fn reset(&mut self) {
+        self.linkages.clear();
+    }
+
+    /// Returns the linkage of the ensemble, replacing the oldest one if the list is full
+    fn linkage_mut(&mut self, ensemble_id: u16) -> &mut DabLinkage {
+        let index = match self
+            .linkages
+            .iter()
+            .position(|linkage| linkage.ensemble_id == ensemble_id)
+        {
+            Some(index) => index,
+            None => {
+                if self.linkages.is_full() {
+                    self.linkages.remove(0);
+                }
+                let _ = self.linkages.push(DabLinkage::new(ensemble_id));
+                self.linkages.len() - 1
+            }
+        };
+        &mut self.linkages[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MAX_DAB_LINKAGES;
+
+    #[test]
+    fn test_ensemble_and_service() {
+        let mut decoder = DabDecoder::new();
+        // Service 0xD210 in ensemble 0xE101, received before the ensemble table
+        decoder.push_group(&Block2(0xC010), &Block3(0xE101), &Block4(0xD210));
+        // Ensemble 0xE101, mode I, 225.648 MHz (14103 * 16 kHz)
+        decoder.push_group(&Block2(0xC004), &Block3(0x3717), &Block4(0xE101));
+        // Service table variant 1 is ignored
+        decoder.push_group(&Block2(0xC011), &Block3(0x1234), &Block4(0xD211));
+
+        let linkages = decoder.linkages();
+        assert_eq!(linkages.len(), 1);
+        assert_eq!(linkages[0].ensemble_id, 0xE101);
+        assert_eq!(linkages[0].frequency, Some(225_648));
+        assert_eq!(linkages[0].mode, DabMode::ModeI);
+        assert_eq!(linkages[0].service_ids, [0xD210]);
+    }
+
+    #[test]
+    fn test_oldest_ensemble_is_replaced() {
+        let mut decoder = DabDecoder::new();
+        for ensemble_id in 0..=MAX_DAB_LINKAGES as u16 {
+            decoder.push_group(&Block2(0xC00F), &Block3(0xFFFF), &Block4(ensemble_id));
+        }
+        let linkages = decoder.linkages();
+        assert_eq!(linkages.len(), MAX_DAB_LINKAGES);
+        assert_eq!(linkages[0].ensemble_id, 1);
+        assert_eq!(linkages[0].frequency, Some(0x3_FFFF * FREQUENCY_STEP_KHZ));
+        assert_eq!(linkages[0].mode, DabMode::ModeIV);
+    }
+}
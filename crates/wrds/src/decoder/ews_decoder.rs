@@ -0,0 +1,113 @@
+use core::fmt;
+
+use crate::types::{Block2, Block3, Block4, EmergencyWarning};
+
+/// Data bits at the end of Block 2 of group 9A
+const BLOCK2_DATA_BITMASK: u16 = 0x1F;
+
+/// Callback receiving every new Emergency Warning System (EWS) message, used to
+/// decode country-specific EWS formats
+pub type EmergencyWarningCallback<'a> = &'a mut dyn FnMut(&EmergencyWarning);
+
+/// Decoder for the Emergency Warning System (EWS) carried in group 9A
+pub struct EwsDecoder<'a> {
+    /// EWS channel identification from group 1A variant 7
+    channel_id: Option<u16>,
+    /// Last message, used to drop repetitions
+    last_message: Option<u64>,
+    callback: Option<EmergencyWarningCallback<'a>>,
+}
+
+impl<'a> EwsDecoder<'a> {
+    pub fn new() -> Self {
+        Self {
+            channel_id: None,
+            last_message: None,
+            callback: None,
+        }
+    }
+
+    pub fn set_callback(&mut self, callback: EmergencyWarningCallback<'a>) {
+        self.callback = Some(callback);
+    }
+
+    /// Push the EWS channel identification from group 1A variant 7
+    pub fn push_channel_id(&mut self, channel_id: u16) {
+        self.channel_id = Some(channel_id);
+    }
+
+    /// Push a group 9A.
+    ///
+    /// Returns the warning unless it repeats the previous message. The callback is
+    /// invoked for every returned warning.
+    pub fn push_group(
+        &mut self,
+        block2: &Block2,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<EmergencyWarning> {
+        let message = (u64::from(block2.0 & BLOCK2_DATA_BITMASK) << 32)
+            | (u64::from(block3.0) << 16)
+            | u64::from(block4.0);
+        if self.last_message == Some(message) {
+            return None;
+        }
+        self.last_message = Some(message);
+
+        let warning = EmergencyWarning {
+            channel_id: self.channel_id,
+            message,
+        };
+        if let Some(callback) = self.callback.as_mut() {
+            callback(&warning);
+        }
+        Some(warning)
+    }
+
+    /// Reset the decoded state, keeping the callback
+    pub fn reset(&mut self) {
+        self.channel_id = None;
+        self.last_message = None;
+    }
+}
+
+impl fmt::Debug for EwsDecoder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EwsDecoder")
+            .field("channel_id", &self.channel_id)
+            .field("last_message", &self.last_message)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        let mut decoder = EwsDecoder::new();
+        decoder.push_channel_id(0x123);
+        let warning = decoder
+            .push_group(&Block2(0x9015), &Block3(0xABCD), &Block4(0x1234))
+            .unwrap();
+        assert_eq!(warning.channel_id, Some(0x123));
+        assert_eq!(warning.message, 0x15_ABCD_1234);
+        assert_eq!(warning.block2_data(), 0x15);
+    }
+
+    #[test]
+    fn test_repetition_is_dropped() {
+        let mut count = 0;
+        let mut callback = |_: &EmergencyWarning| count += 1;
+        let mut decoder = EwsDecoder::new();
+        decoder.set_callback(&mut callback);
+        let group = (Block2(0x9001), Block3(0x0002), Block4(0x0003));
+        assert!(decoder.push_group(&group.0, &group.1, &group.2).is_some());
+        assert!(decoder.push_group(&group.0, &group.1, &group.2).is_none());
+        decoder.reset();
+        assert!(decoder.push_group(&group.0, &group.1, &group.2).is_some());
+        assert_eq!(count, 2);
+    }
+}
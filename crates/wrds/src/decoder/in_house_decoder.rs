@@ -0,0 +1,108 @@
+use core::fmt;
+
+use crate::types::{Block2, Block3, Block4, GroupVariant, InHouseData, ProgrammeIdentifier};
+
+/// Data bits at the end of Block 2 of group 6
+const BLOCK2_DATA_BITMASK: u16 = 0x1F;
+
+/// Callback receiving the payload of every in-house group 6A and 6B
+pub type InHouseCallback<'a> = &'a mut dyn FnMut(&InHouseData);
+
+/// Decoder for the in-house applications carried in group 6.
+///
+/// The content is defined by the broadcaster, so repeated groups are not dropped.
+pub struct InHouseDecoder<'a> {
+    callback: Option<InHouseCallback<'a>>,
+}
+
+impl<'a> InHouseDecoder<'a> {
+    pub fn new() -> Self {
+        Self { callback: None }
+    }
+
+    pub fn set_callback(&mut self, callback: InHouseCallback<'a>) {
+        self.callback = Some(callback);
+    }
+
+    /// Push a group 6A or 6B received under `pi`.
+    ///
+    /// Group 6A carries data in Blocks 3 and 4, group 6B only in Block 4, so
+    /// `block3` must only be provided for group 6A. The payload is passed to the
+    /// callback if one is set, and returned otherwise.
+    pub fn push_group(
+        &mut self,
+        pi: Option<ProgrammeIdentifier>,
+        block2: &Block2,
+        block3: Option<&Block3>,
+        block4: &Block4,
+    ) -> Option<InHouseData> {
+        let data = InHouseData {
+            pi,
+            variant: match block3 {
+                Some(_) => GroupVariant::A,
+                None => GroupVariant::B,
+            },
+            block2_data: (block2.0 & BLOCK2_DATA_BITMASK) as u8,
+            block3: block3.map(|block3| block3.0),
+            block4: block4.0,
+        };
+        match self.callback.as_mut() {
+            Some(callback) => {
+                callback(&data);
+                None
+            }
+            None => Some(data),
+        }
+    }
+}
+
+impl fmt::Debug for InHouseDecoder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InHouseDecoder")
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload() {
+        let mut decoder = InHouseDecoder::new();
+        let pi = Some(ProgrammeIdentifier(0x1234));
+        let data = decoder
+            .push_group(pi, &Block2(0x6015), Some(&Block3(0xABCD)), &Block4(0x5678))
+            .unwrap();
+        assert_eq!(data.pi, pi);
+        assert_eq!(data.variant, GroupVariant::A);
+        assert_eq!(data.block2_data, 0x15);
+        assert_eq!(data.block3, Some(0xABCD));
+        assert_eq!(data.block4, 0x5678);
+
+        let data = decoder
+            .push_group(pi, &Block2(0x6802), None, &Block4(0x9ABC))
+            .unwrap();
+        assert_eq!(data.variant, GroupVariant::B);
+        assert_eq!(data.block3, None);
+    }
+
+    #[test]
+    fn test_callback() {
+        let mut payloads = Vec::new();
+        let mut callback = |data: &InHouseData| payloads.push(*data);
+        {
+            let mut decoder = InHouseDecoder::new();
+            decoder.set_callback(&mut callback);
+            let pi = Some(ProgrammeIdentifier(0x1234));
+            // Payloads only go to the callback, including repeated ones
+            for _ in 0..2 {
+                let data = decoder.push_group(pi, &Block2(0x6802), None, &Block4(0x9ABC));
+                assert_eq!(data, None);
+            }
+        }
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0], payloads[1]);
+    }
+}
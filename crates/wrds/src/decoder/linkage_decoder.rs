@@ -0,0 +1,169 @@
+use crate::types::{
+    Block2, Block3, Block4, LinkageInfo, LinkedProgrammeList, NetworkLinkage, NetworkLinkageList,
+    ProgrammeIdentifier,
+};
+
+/// Variant code in Block 2 of group 14A
+const VARIANT_BITMASK: u16 = 0xF;
+const LINKAGE_VARIANT: u16 = 12;
+
+/// Country code in the first 4 bits of the PI
+const COUNTRY_CODE_SHIFT: usize = 12;
+
+/// Decoder for programme linkage from group 1A and group 14A variant 12.
+///
+/// Group 14A variant 12 carries the linkage information of the network whose PI is
+/// in Block 4, which is the tuned network itself when it matches the tuned PI.
+#[derive(Debug)]
+pub struct LinkageDecoder {
+    /// Linkage Actuator (LA) of the tuned network from group 1A
+    linkage_actuator: Option<bool>,
+    networks: NetworkLinkageList,
+}
+
+impl LinkageDecoder {
+    pub fn new() -> Self {
+        Self {
+            linkage_actuator: None,
+            networks: NetworkLinkageList::new(),
+        }
+    }
+
+    /// Push the Linkage Actuator (LA) of the tuned network from Block 3 of group 1A
+    pub fn push_linkage_actuator(&mut self, linkage_actuator: bool) {
+        self.linkage_actuator = Some(linkage_actuator);
+    }
+
+    /// Push a group 14A, ignoring variants other than linkage information
+    pub fn push_group(&mut self, block2: &Block2, block3: &Block3, block4: &Block4) {
+        if block2.0 & VARIANT_BITMASK != LINKAGE_VARIANT {
+            return;
+        }
+        let linkage = NetworkLinkage {
+            pi: ProgrammeIdentifier(block4.0),
+            info: LinkageInfo::from(block3.0),
+        };
+        match self
+            .networks
+            .iter_mut()
+            .find(|network| network.pi == linkage.pi)
+        {
+            Some(network) => network.info = linkage.info,
+            None => {
+                if self.networks.is_full() {
+                    self.networks.remove(0);
+                }
+                let _ = self.networks.push(linkage);
+            }
+        }
+    }
+
+    /// Returns the Linkage Actuator (LA) of the tuned network, if received
+    pub fn linkage_actuator(&self) -> Option<bool> {
+        self.linkage_actuator
+    }
+
+    /// Returns the linkage information received for each network
+    pub fn networks(&self) -> &NetworkLinkageList {
+        &self.networks
+    }
+
+    /// Returns the PIs of the other networks currently carrying the programme of
+    /// the tuned network `pi`.
+    ///
+    /// The tuned network must be linked, as signalled by the LA bit of group 1A or
+    /// of its own linkage information. Networks outside of an international linkage
+    /// set must share the country code of the tuned network.
+    pub fn linked_programmes(&self, pi: ProgrammeIdentifier) -> LinkedProgrammeList {
+        let mut linked = LinkedProgrammeList::new();
+        let Some(tuned) = self.networks.iter().find(|network| network.pi == pi) else {
+            return linked;
+        };
+        if !self.linkage_actuator.unwrap_or(tuned.info.linkage_actuator) {
+            return linked;
+        }
+
+        let same_country = |other: ProgrammeIdentifier| {
+            other.0 >> COUNTRY_CODE_SHIFT == pi.0 >> COUNTRY_CODE_SHIFT
+        };
+        for network in &self.networks {
+            if network.pi != pi
+                && network.info.linkage_actuator
+                && network.info.same_set(&tuned.info)
+                && (tuned.info.international || same_country(network.pi))
+            {
+                let _ = linked.push(network.pi);
+            }
+        }
+        linked
+    }
+
+    pub fn reset(&mut self) {
+        self.linkage_actuator = None;
+        self.networks.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TUNED_PI: ProgrammeIdentifier = ProgrammeIdentifier(0xD301);
+
+    fn push_linkage(decoder: &mut LinkageDecoder, pi: u16, info: u16) {
+        decoder.push_group(&Block2(0xE00C), &Block3(info), &Block4(pi));
+    }
+
+    #[test]
+    fn test_linkage_info() {
+        let info = LinkageInfo::from(0xE123);
+        assert!(info.linkage_actuator);
+        assert!(info.extended_generic);
+        assert!(info.international);
+        assert_eq!(info.linkage_set_number, 0x123);
+
+        let mut decoder = LinkageDecoder::new();
+        // Variant 0 is ignored
+        decoder.push_group(&Block2(0xE000), &Block3(0x8123), &Block4(0xD302));
+        push_linkage(&mut decoder, 0xD302, 0x8123);
+        push_linkage(&mut decoder, 0xD302, 0x8124);
+        assert_eq!(decoder.networks().len(), 1);
+        assert_eq!(decoder.networks()[0].info.linkage_set_number, 0x124);
+    }
+
+    #[test]
+    fn test_linked_programmes() {
+        let mut decoder = LinkageDecoder::new();
+        push_linkage(&mut decoder, TUNED_PI.0, 0x8042);
+        // Linked
+        push_linkage(&mut decoder, 0xD302, 0x8042);
+        // Not currently linked
+        push_linkage(&mut decoder, 0xD303, 0x0042);
+        // Different linkage set
+        push_linkage(&mut decoder, 0xD304, 0x8043);
+        // Extended generic linkage set
+        push_linkage(&mut decoder, 0xD305, 0xC042);
+        // Different country outside of an international set
+        push_linkage(&mut decoder, 0xA306, 0x8042);
+
+        assert_eq!(
+            decoder.linked_programmes(TUNED_PI),
+            [ProgrammeIdentifier(0xD302)]
+        );
+
+        decoder.push_linkage_actuator(false);
+        assert!(decoder.linked_programmes(TUNED_PI).is_empty());
+    }
+
+    #[test]
+    fn test_international_linkage() {
+        let mut decoder = LinkageDecoder::new();
+        decoder.push_linkage_actuator(true);
+        push_linkage(&mut decoder, TUNED_PI.0, 0x2042);
+        push_linkage(&mut decoder, 0xA306, 0xA042);
+        assert_eq!(
+            decoder.linked_programmes(TUNED_PI),
+            [ProgrammeIdentifier(0xA306)]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use core::fmt;
+
+#[cfg(feature = "unstable")]
+use crate::types::TypeCGroup;
+use crate::{
+    decoder::oda_identifier::OdaApplication,
+    types::{Block2, Block3, Block4, GroupType, GroupVariant},
+};
+
+/// Maximum number of user-registered ODA handlers
+pub const MAX_ODA_HANDLERS: usize = 8;
+
+/// Group carrying data for an Open Data Application (ODA)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OdaGroup {
+    pub group_type: GroupType,
+    pub group_variant: GroupVariant,
+    pub block2: Block2,
+    pub block3: Option<Block3>,
+    pub block4: Option<Block4>,
+}
+
+impl OdaGroup {
+    /// Returns the 5 application bits at the end of Block 2.
+    pub fn block2_data(&self) -> u8 {
+        const DATA_BITMASK: u16 = 0x1F;
+        (self.block2.0 & DATA_BITMASK) as u8
+    }
+}
+
+/// Handler for an Open Data Application (ODA) decoded outside of this crate.
+///
+/// Handlers are registered on the [`crate::Decoder`] by their Application Identifier (AID).
+/// Once group 3A announces the AID, every group of the assigned type and variant is passed
+/// to the handler.
+pub trait OdaHandler {
+    /// Application Identifier (AID) decoded by this handler.
+    fn aid(&self) -> u16;
+
+    /// Called whenever group 3A announces the application.
+    ///
+    /// `message` contains the 16-bit message bits from Block 3 of group 3A.
+    fn identify(&mut self, group_type: GroupType, group_variant: GroupVariant, message: u16) {
+        let _ = (group_type, group_variant, message);
+    }
+
+    /// Called for every group of the type and variant assigned to the application.
+    fn decode_group(&mut self, group: &OdaGroup);
+
+    /// Called for every RDS2 type C group on a channel assigned to the application.
+    ///
+    /// Handlers for [`crate::RFT_AID`] receive the groups of all RFT pipes.
+    #[cfg(feature = "unstable")]
+    fn decode_type_c_group(&mut self, group: &TypeCGroup) {
+        let _ = group;
+    }
+}
+
+/// Callback receiving the AID and raw blocks of groups carrying an ODA that is neither
+/// decoded by this crate nor handled by a registered handler
+pub type UnknownOdaCallback<'a> = &'a mut dyn FnMut(u16, &OdaGroup);
+
+/// User-registered ODA handlers
+pub struct OdaHandlers<'a> {
+    handlers: heapless::Vec<&'a mut dyn OdaHandler, MAX_ODA_HANDLERS>,
+    unknown: Option<UnknownOdaCallback<'a>>,
+}
+
+impl<'a> OdaHandlers<'a> {
+    pub fn new() -> Self {
+        Self {
+            handlers: heapless::Vec::new(),
+            unknown: None,
+        }
+    }
+
+    /// Set the callback for groups carrying an unknown ODA without a registered handler.
+    pub fn set_unknown_callback(&mut self, callback: UnknownOdaCallback<'a>) {
+        self.unknown = Some(callback);
+    }
+
+    /// Register a new handler
+    ///
+    /// # Errors
+    /// Returns the handler back if [`MAX_ODA_HANDLERS`] handlers are already registered.
+    pub fn register(
+        &mut self,
+        handler: &'a mut dyn OdaHandler,
+    ) -> Result<(), &'a mut dyn OdaHandler> {
+        self.handlers.push(handler)
+    }
+
+    /// Returns true if any handler is registered for the AID.
+    pub fn contains(&self, aid: u16) -> bool {
+        self.handlers.iter().any(|handler| handler.aid() == aid)
+    }
+
+    /// Notify all handlers for the AID that the application was announced in group 3A.
+    pub fn identify(
+        &mut self,
+        aid: u16,
+        group_type: GroupType,
+        group_variant: GroupVariant,
+        message: u16,
+    ) {
+        self.handlers
+            .iter_mut()
+            .filter(|handler| handler.aid() == aid)
+            .for_each(|handler| handler.identify(group_type, group_variant, message));
+    }
+
+    /// Pass the group to all handlers for the AID of the application.
+    ///
+    /// The unknown ODA callback is used instead if no handler is registered for an
+    /// application that is not decoded by this crate.
+    pub fn decode_group(&mut self, app: OdaApplication, group: &OdaGroup) {
+        let aid = app.aid();
+        if !self.contains(aid) {
+            if let (OdaApplication::Other(_), Some(callback)) = (app, self.unknown.as_mut()) {
+                callback(aid, group);
+            }
+            return;
+        }
+        self.handlers
+            .iter_mut()
+            .filter(|handler| handler.aid() == aid)
+            .for_each(|handler| handler.decode_group(group));
+    }
+
+    /// Pass a type C group to all handlers for the AID.
+    #[cfg(feature = "unstable")]
+    pub fn decode_type_c_group(&mut self, aid: u16, group: &TypeCGroup) {
+        self.handlers
+            .iter_mut()
+            .filter(|handler| handler.aid() == aid)
+            .for_each(|handler| handler.decode_type_c_group(group));
+    }
+}
+
+impl fmt::Debug for OdaHandlers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OdaHandlers")
+            .field(
+                "handlers",
+                &self
+                    .handlers
+                    .iter()
+                    .map(|handler| handler.aid())
+                    .collect::<heapless::Vec<u16, MAX_ODA_HANDLERS>>(),
+            )
+            .field("unknown", &self.unknown.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct CountingHandler {
+        aid: u16,
+        identified: Option<u16>,
+        groups: usize,
+    }
+
+    impl OdaHandler for CountingHandler {
+        fn aid(&self) -> u16 {
+            self.aid
+        }
+
+        fn identify(&mut self, _: GroupType, _: GroupVariant, message: u16) {
+            self.identified = Some(message);
+        }
+
+        fn decode_group(&mut self, _: &OdaGroup) {
+            self.groups += 1;
+        }
+    }
+
+    fn group() -> OdaGroup {
+        OdaGroup {
+            group_type: GroupType(11),
+            group_variant: GroupVariant::A,
+            block2: Block2(0xB41F),
+            block3: None,
+            block4: None,
+        }
+    }
+
+    #[test]
+    fn test_dispatch_by_aid() {
+        let mut handler1 = CountingHandler {
+            aid: 0x1234,
+            identified: None,
+            groups: 0,
+        };
+        let mut handler2 = CountingHandler {
+            aid: 0x5678,
+            identified: None,
+            groups: 0,
+        };
+        {
+            let mut handlers = OdaHandlers::new();
+            assert!(handlers.register(&mut handler1).is_ok());
+            assert!(handlers.register(&mut handler2).is_ok());
+            assert!(handlers.contains(0x1234));
+            assert!(!handlers.contains(0xABCD));
+            handlers.identify(0x1234, GroupType(11), GroupVariant::A, 0xBEEF);
+            handlers.decode_group(OdaApplication::Other(0x1234), &group());
+            handlers.decode_group(OdaApplication::Other(0x1234), &group());
+        }
+        assert_eq!(handler1.identified, Some(0xBEEF));
+        assert_eq!(handler1.groups, 2);
+        assert_eq!(handler2.identified, None);
+        assert_eq!(handler2.groups, 0);
+    }
+
+    #[test]
+    fn test_unknown_callback_without_handler() {
+        let mut handler = CountingHandler {
+            aid: 0x1234,
+            identified: None,
+            groups: 0,
+        };
+        let mut unknown_aids = Vec::new();
+        let mut callback = |aid: u16, _: &OdaGroup| unknown_aids.push(aid);
+        {
+            let mut handlers = OdaHandlers::new();
+            handlers.set_unknown_callback(&mut callback);
+            assert!(handlers.register(&mut handler).is_ok());
+            handlers.decode_group(OdaApplication::Other(0x1234), &group());
+            handlers.decode_group(OdaApplication::Other(0x5678), &group());
+            // Applications decoded by this crate are not unknown
+            handlers.decode_group(OdaApplication::RtPlus, &group());
+        }
+        assert_eq!(handler.groups, 1);
+        assert_eq!(unknown_aids, vec![0x5678]);
+    }
+
+    #[test]
+    fn test_block2_data() {
+        assert_eq!(group().block2_data(), 0x1F);
+    }
+}
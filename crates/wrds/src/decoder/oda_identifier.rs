@@ -2,7 +2,12 @@ use core::{error::Error, fmt};
 
 use heapless::index_map::FnvIndexMap;
 
-use crate::types::{GroupType, GroupVariant};
+use crate::types::{
+    GroupType, GroupVariant, OdaAnnouncement, OdaAnnouncementList, MAX_ODA_ANNOUNCEMENTS,
+};
+
+mod registry;
+pub use registry::{lookup_aid, registered_odas, OdaInfo};
 
 /// ODA (Open Data Applications) application error
 #[derive(Debug, PartialEq, Eq)]
@@ -29,11 +34,42 @@ impl Error for OdaError {}
 /// RadioText Plus application identifier (AID)
 const RT_PLUS_AID: u16 = 0x4BD7;
 
+/// Traffic Message Channel application identifiers (AID)
+const TMC_AID: u16 = 0xCD46;
+const TMC_ALTERNATIVE_AID: u16 = 0xCD47;
+
+/// DAB cross-referencing application identifier (AID)
+const DAB_AID: u16 = 0x0093;
+
 /// ODA (Open Data Applications) application types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OdaApplication {
     /// Radio Text Plus (0x4BD7)
     RtPlus,
+    /// Traffic Message Channel (0xCD46 or 0xCD47) with its AID
+    Tmc(u16),
+    /// DAB cross-referencing (0x0093)
+    Dab,
+    /// Application not decoded by this crate.
+    ///
+    /// These are passed on to a user-registered [`crate::OdaHandler`] if available.
+    Other(u16),
+}
+
+impl OdaApplication {
+    /// Returns the application for the AID, falling back to [`OdaApplication::Other`]
+    pub fn from_aid(aid: u16) -> Self {
+        Self::try_from(aid).unwrap_or(Self::Other(aid))
+    }
+
+    /// Returns the application identifier (AID)
+    pub fn aid(&self) -> u16 {
+        match self {
+            Self::RtPlus => RT_PLUS_AID,
+            Self::Dab => DAB_AID,
+            Self::Tmc(aid) | Self::Other(aid) => *aid,
+        }
+    }
 }
 
 impl TryFrom<u16> for OdaApplication {
@@ -42,6 +78,8 @@ impl TryFrom<u16> for OdaApplication {
     fn try_from(value: u16) -> Result<Self, Self::Error> {
         match value {
             RT_PLUS_AID => Ok(OdaApplication::RtPlus),
+            TMC_AID | TMC_ALTERNATIVE_AID => Ok(OdaApplication::Tmc(value)),
+            DAB_AID => Ok(OdaApplication::Dab),
             _ => Err(OdaError::UnknownAid(value)),
         }
     }
@@ -54,14 +92,33 @@ struct OdaKey {
     group_variant: GroupVariant,
 }
 
-/// Maximum number of ODA applications to track
+/// ODA application registered to a group type and variant
+#[derive(Debug, Clone, Copy)]
+struct OdaEntry {
+    app: OdaApplication,
+    /// Number of groups since the last group 3A announcement
+    age: u32,
+}
+
+/// Upper limit of ODA applications to track
 ///
 /// This must be a power of 2
-const MAX_ODA_APPS: usize = 4;
+pub const MAX_ODA_APPS: usize = 16;
+
+/// Default number of ODA applications to track
+pub const DEFAULT_ODA_CAPACITY: usize = 8;
+
+/// Default number of groups without a group 3A repeat before an application expires.
+///
+/// This is roughly two minutes at 11.4 groups per second.
+pub const DEFAULT_ODA_TIMEOUT: u32 = 1368;
 
 #[derive(Debug)]
 pub struct OdaIdentifier {
-    app_map: FnvIndexMap<OdaKey, OdaApplication, MAX_ODA_APPS>,
+    app_map: FnvIndexMap<OdaKey, OdaEntry, MAX_ODA_APPS>,
+    announced: heapless::Vec<(OdaAnnouncement, u32), MAX_ODA_ANNOUNCEMENTS>,
+    capacity: usize,
+    timeout: u32,
 }
 
 impl OdaIdentifier {
@@ -69,13 +126,85 @@ impl OdaIdentifier {
     pub fn new() -> Self {
         Self {
             app_map: FnvIndexMap::new(),
+            announced: heapless::Vec::new(),
+            capacity: DEFAULT_ODA_CAPACITY,
+            timeout: DEFAULT_ODA_TIMEOUT,
+        }
+    }
+
+    /// Set the maximum number of tracked ODA applications
+    ///
+    /// Already registered applications beyond the new capacity are dropped.
+    ///
+    /// # Errors
+    /// Returns an error if the capacity exceeds [`MAX_ODA_APPS`]
+    pub fn set_capacity(&mut self, capacity: usize) -> Result<(), OdaError> {
+        if capacity > MAX_ODA_APPS {
+            return Err(OdaError::MaxAppsExceeded);
+        }
+        self.capacity = capacity;
+        self.app_map.truncate(capacity);
+        Ok(())
+    }
+
+    /// Set the number of groups without a group 3A repeat before an application expires
+    pub fn set_timeout(&mut self, timeout: u32) {
+        self.timeout = timeout;
+    }
+
+    /// Age all registrations by one group and remove the expired ones
+    pub fn tick(&mut self) {
+        let timeout = self.timeout;
+        self.app_map.retain(|_, entry| {
+            entry.age = entry.age.saturating_add(1);
+            entry.age <= timeout
+        });
+        self.announced.retain_mut(|(_, age)| {
+            *age = age.saturating_add(1);
+            *age <= timeout
+        });
+    }
+
+    /// Remove all registrations and announcements
+    ///
+    /// This should be called after tuning to a different station.
+    pub fn reset(&mut self) {
+        self.app_map.clear();
+        self.announced.clear();
+    }
+
+    /// Record an application announced in group 3A.
+    ///
+    /// Announcements for an already known AID replace the previous one.
+    /// New AIDs are dropped once [`MAX_ODA_ANNOUNCEMENTS`] is reached.
+    pub fn announce(&mut self, announcement: OdaAnnouncement) {
+        match self
+            .announced
+            .iter_mut()
+            .find(|(existing, _)| existing.aid() == announcement.aid())
+        {
+            Some(existing) => *existing = (announcement, 0),
+            None => {
+                let _ = self.announced.push((announcement, 0));
+            }
         }
     }
 
+    /// Returns every application announced and not yet expired
+    pub fn announced(&self) -> OdaAnnouncementList {
+        self.announced
+            .iter()
+            .map(|(announcement, _)| *announcement)
+            .collect()
+    }
+
     /// Add a new ODA application with the given group type, variant, and AID
     ///
+    /// Re-adding an application refreshes its age. Any other group type
+    /// previously assigned to the same application is released.
+    ///
     /// # Errors
-    /// Returns an error if the AID is unknown or if the maximum number of applications is exceeded
+    /// Returns an error if the maximum number of applications is exceeded
     pub fn add_new_app(
         &mut self,
         group_type: GroupType,
@@ -87,7 +216,12 @@ impl OdaIdentifier {
             group_variant,
         };
         self.app_map
-            .insert(key, app)
+            .retain(|existing_key, entry| entry.app != app || *existing_key == key);
+        if !self.app_map.contains_key(&key) && self.app_map.len() >= self.capacity {
+            return Err(OdaError::MaxAppsExceeded);
+        }
+        self.app_map
+            .insert(key, OdaEntry { app, age: 0 })
             .map_err(|_| OdaError::MaxAppsExceeded)?;
         Ok(())
     }
@@ -112,6 +246,126 @@ impl OdaIdentifier {
             group_type,
             group_variant,
         };
-        self.app_map.get(&key).copied()
+        self.app_map.get(&key).map(|entry| entry.app)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_application_from_aid() {
+        assert_eq!(OdaApplication::from_aid(0x4BD7), OdaApplication::RtPlus);
+        assert_eq!(
+            OdaApplication::from_aid(0xCD46),
+            OdaApplication::Tmc(0xCD46)
+        );
+        assert_eq!(
+            OdaApplication::from_aid(0xCD47),
+            OdaApplication::Tmc(0xCD47)
+        );
+        assert_eq!(OdaApplication::Tmc(0xCD47).aid(), 0xCD47);
+        assert_eq!(OdaApplication::from_aid(0x0093), OdaApplication::Dab);
+        assert_eq!(
+            OdaApplication::from_aid(0x1234),
+            OdaApplication::Other(0x1234)
+        );
+        assert_eq!(OdaApplication::Other(0x1234).aid(), 0x1234);
+    }
+
+    #[test]
+    fn test_announce_replaces_existing_aid() {
+        let mut identifier = OdaIdentifier::new();
+        identifier.announce(OdaAnnouncement::new(
+            0x1234,
+            GroupType(11),
+            GroupVariant::A,
+            0x0001,
+        ));
+        identifier.announce(OdaAnnouncement::new(
+            0x4BD7,
+            GroupType(12),
+            GroupVariant::A,
+            0x0000,
+        ));
+        identifier.announce(OdaAnnouncement::new(
+            0x1234,
+            GroupType(11),
+            GroupVariant::A,
+            0x0002,
+        ));
+        assert_eq!(
+            identifier.announced(),
+            OdaAnnouncementList::from_array([
+                OdaAnnouncement::new(0x1234, GroupType(11), GroupVariant::A, 0x0002),
+                OdaAnnouncement::new(0x4BD7, GroupType(12), GroupVariant::A, 0x0000),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut identifier = OdaIdentifier::new();
+        identifier.set_capacity(1).unwrap();
+        assert_eq!(
+            identifier.add_new_app(GroupType(11), GroupVariant::A, OdaApplication::RtPlus),
+            Ok(())
+        );
+        assert_eq!(
+            identifier.add_new_app(GroupType(12), GroupVariant::A, OdaApplication::Other(1)),
+            Err(OdaError::MaxAppsExceeded)
+        );
+        assert_eq!(
+            identifier.set_capacity(MAX_ODA_APPS + 1),
+            Err(OdaError::MaxAppsExceeded)
+        );
+    }
+
+    #[test]
+    fn test_app_moves_to_new_group() {
+        let mut identifier = OdaIdentifier::new();
+        let _ = identifier.add_new_app(GroupType(11), GroupVariant::A, OdaApplication::RtPlus);
+        let _ = identifier.add_new_app(GroupType(12), GroupVariant::A, OdaApplication::RtPlus);
+        assert!(!identifier.is_registered(GroupType(11), GroupVariant::A));
+        assert!(identifier.is_registered(GroupType(12), GroupVariant::A));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut identifier = OdaIdentifier::new();
+        identifier.set_timeout(2);
+        let _ = identifier.add_new_app(GroupType(11), GroupVariant::A, OdaApplication::RtPlus);
+        identifier.announce(OdaAnnouncement::new(
+            0x4BD7,
+            GroupType(11),
+            GroupVariant::A,
+            0x0000,
+        ));
+        identifier.tick();
+        identifier.tick();
+        assert!(identifier.is_registered(GroupType(11), GroupVariant::A));
+        let _ = identifier.add_new_app(GroupType(11), GroupVariant::A, OdaApplication::RtPlus);
+        identifier.tick();
+        identifier.tick();
+        assert!(identifier.is_registered(GroupType(11), GroupVariant::A));
+        assert!(identifier.announced().is_empty());
+        identifier.tick();
+        assert!(!identifier.is_registered(GroupType(11), GroupVariant::A));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut identifier = OdaIdentifier::new();
+        let _ = identifier.add_new_app(GroupType(11), GroupVariant::A, OdaApplication::RtPlus);
+        identifier.announce(OdaAnnouncement::new(
+            0x4BD7,
+            GroupType(11),
+            GroupVariant::A,
+            0x0000,
+        ));
+        identifier.reset();
+        assert!(!identifier.is_registered(GroupType(11), GroupVariant::A));
+        assert!(identifier.announced().is_empty());
     }
 }
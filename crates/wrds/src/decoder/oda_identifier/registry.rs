@@ -0,0 +1,123 @@
+/// Entry in the registered Open Data Application (ODA) list
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OdaInfo {
+    /// Application Identifier (AID)
+    pub aid: u16,
+    /// Name of the application
+    pub name: &'static str,
+    /// Specification defining the application
+    pub specification: &'static str,
+}
+
+const fn entry(aid: u16, name: &'static str, specification: &'static str) -> OdaInfo {
+    OdaInfo {
+        aid,
+        name,
+        specification,
+    }
+}
+
+/// Registered ODAs, sorted by AID.
+const REGISTRY: &[OdaInfo] = &[
+    entry(0x0093, "DAB cross-referencing", "ETSI EN 301 700"),
+    entry(
+        0x0BCB,
+        "Leisure & Practical Info for Drivers",
+        "IEC 62106-6",
+    ),
+    entry(
+        0x0C24,
+        "Transmitter network group designation",
+        "IEC 62106-6",
+    ),
+    entry(
+        0x0D45,
+        "Traffic Message Channel (test)",
+        "ISO 14819-1 (RDS-TMC ALERT-C)",
+    ),
+    entry(0x0D8B, "Electronic road toll collection", "IEC 62106-6"),
+    entry(0x0F87, "NRSC song title and artist", "NRSC-4"),
+    entry(0x1C68, "ITIS in-vehicle database", "IEC 62106-6"),
+    entry(0x1DC5, "Encrypted TTI using ALERT-Plus", "ISO 14819-1"),
+    entry(0x4400, "RDS-Light", "IEC 62106-6"),
+    entry(0x4AA1, "RASANT", "IEC 62106-6"),
+    entry(0x4BD7, "RadioText Plus", "IEC 62106-6 (RT+)"),
+    entry(0x4BD8, "RadioText Plus for eRT", "IEC 62106-6 (RT+)"),
+    entry(0x4D87, "Radio Commerce System", "IEC 62106-6"),
+    entry(0x50DD, "Disaster warning", "IEC 62106-6"),
+    entry(0x6363, "Hybrid Radio", "ETSI TS 103 270"),
+    entry(0x6365, "RDS2 9-bit AF lists", "IEC 62106-2"),
+    entry(0x6552, "Enhanced RadioText", "IEC 62106-6 (eRT)"),
+    entry(0x6A7A, "Warning receiver", "IEC 62106-6"),
+    entry(0x7373, "Enhanced early warning system", "IEC 62106-6"),
+    entry(
+        0xA911,
+        "Data FM selective multipoint messaging",
+        "IEC 62106-6",
+    ),
+    entry(0xC3A1, "Personal Radio Service", "IEC 62106-6"),
+    entry(0xC3B0, "iTunes tagging", "Apple iTunes Tagging"),
+    entry(0xC3C3, "NAVTEQ Traffic Plus", "NAVTEQ"),
+    entry(0xC4D4, "eEAS", "IEC 62106-6"),
+    entry(0xC549, "Smart grid broadcast channel", "IEC 62106-6"),
+    entry(0xC563, "ID Logic", "IEC 62106-6"),
+    entry(0xC737, "Utility Message Channel", "IEC 62106-6"),
+    entry(
+        0xCD46,
+        "Traffic Message Channel",
+        "ISO 14819-1 (RDS-TMC ALERT-C)",
+    ),
+    entry(
+        0xCD47,
+        "Traffic Message Channel",
+        "ISO 14819-1 (RDS-TMC ALERT-C)",
+    ),
+    entry(0xCE6B, "Encrypted TTI using ALERT-Plus", "ISO 14819-1"),
+    entry(0xE123, "APS gateway", "IEC 62106-6"),
+    entry(0xE1C1, "Action code", "IEC 62106-6"),
+    entry(0xE411, "Beacon downlink", "IEC 62106-6"),
+    entry(0xE911, "EAS open protocol", "IEC 62106-6"),
+    entry(
+        0xFF7F,
+        "RDS2 file transfer (station logo)",
+        "IEC 62106-2 (RFT)",
+    ),
+    entry(
+        0xFF80,
+        "RDS2 file transfer (slideshow)",
+        "IEC 62106-2 (RFT)",
+    ),
+];
+
+/// Look up the registered name and specification of an application identifier (AID).
+///
+/// Returns `None` if the AID is not in the registry.
+pub fn lookup_aid(aid: u16) -> Option<&'static OdaInfo> {
+    REGISTRY
+        .binary_search_by_key(&aid, |info| info.aid)
+        .ok()
+        .map(|index| &REGISTRY[index])
+}
+
+/// Returns every registered ODA, sorted by AID.
+pub fn registered_odas() -> &'static [OdaInfo] {
+    REGISTRY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted() {
+        assert!(REGISTRY.windows(2).all(|pair| pair[0].aid < pair[1].aid));
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup_aid(0xCD46).unwrap().name, "Traffic Message Channel");
+        assert_eq!(lookup_aid(0x4BD7).unwrap().name, "RadioText Plus");
+        assert_eq!(lookup_aid(0x0093).unwrap().name, "DAB cross-referencing");
+        assert_eq!(lookup_aid(0x1234), None);
+    }
+}
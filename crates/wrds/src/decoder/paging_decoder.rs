@@ -0,0 +1,142 @@
+//! Radio Paging (RP) in groups 1A, 7A and 13A.
+//!
+//! Group 1A signals paging through the radio paging codes at the end of Block 2:
+//! the transmitter network group designation in bits 4 to 2 and the battery saving
+//! interval in bits 1 and 0. Slow labelling variants 0 and 2 carry the paging
+//! operator and area codes.
+//!
+//! The paging messages of group 7A and the enhanced paging system information of
+//! group 13A are only decoded with the `unstable` feature, see [`messages`] and
+//! [`enhanced`].
+
+use crate::types::{Block2, PagingInfo};
+#[cfg(feature = "unstable")]
+use crate::types::{Block3, Block4, PagingMessage};
+
+#[cfg(feature = "unstable")]
+mod enhanced;
+#[cfg(feature = "unstable")]
+mod messages;
+
+/// Transmitter network group designation in Block 2 of group 1A
+const NETWORK_GROUP_SHIFT: usize = 2;
+const NETWORK_GROUP_BITMASK: u16 = 0x7;
+
+/// Battery saving interval in Block 2 of group 1A
+const BATTERY_SAVING_BITMASK: u16 = 0x3;
+
+/// Paging operator code (OPC) in slow labelling variant 2
+const OPC_SHIFT: usize = 8;
+const OPC_BITMASK: u16 = 0xF;
+
+/// Paging area code (PAC) in slow labelling variant 2
+const PAC_BITMASK: u16 = 0x3F;
+
+/// Decoder for Radio Paging (RP)
+#[derive(Debug)]
+pub struct PagingDecoder {
+    info: Option<PagingInfo>,
+    #[cfg(feature = "unstable")]
+    messages: messages::MessageDecoder,
+}
+
+impl PagingDecoder {
+    pub fn new() -> Self {
+        Self {
+            info: None,
+            #[cfg(feature = "unstable")]
+            messages: messages::MessageDecoder::new(),
+        }
+    }
+
+    /// Push the radio paging codes from Block 2 of group 1A
+    pub fn push_paging_codes(&mut self, block2: &Block2) {
+        let codes =
+            block2.0 & ((NETWORK_GROUP_BITMASK << NETWORK_GROUP_SHIFT) | BATTERY_SAVING_BITMASK);
+        // Stations without paging transmit all zeros
+        if codes == 0 {
+            return;
+        }
+        let info = self.info.get_or_insert_with(PagingInfo::default);
+        info.network_group = ((codes >> NETWORK_GROUP_SHIFT) & NETWORK_GROUP_BITMASK) as u8;
+        info.battery_saving_interval = (codes & BATTERY_SAVING_BITMASK) as u8;
+    }
+
+    /// Push the paging operator code from slow labelling variant 0
+    pub fn push_operator_code(&mut self, operator_code: u8) {
+        if operator_code != 0 {
+            self.info
+                .get_or_insert_with(PagingInfo::default)
+                .operator_code = Some(operator_code);
+        }
+    }
+
+    /// Push the paging identification from slow labelling variant 2
+    pub fn push_paging_identification(&mut self, data: u16) {
+        let info = self.info.get_or_insert_with(PagingInfo::default);
+        info.operator_code = Some(((data >> OPC_SHIFT) & OPC_BITMASK) as u8);
+        info.area_code = Some((data & PAC_BITMASK) as u8);
+    }
+
+    /// Push a group 13A carrying enhanced paging information
+    #[cfg(feature = "unstable")]
+    pub fn push_enhanced_group(&mut self, block2: &Block2, block3: &Block3) {
+        if let Some(enhanced) = enhanced::system_information(block2, block3) {
+            self.info.get_or_insert_with(PagingInfo::default).enhanced = Some(enhanced);
+        }
+    }
+
+    /// Returns the paging parameters once the station signals paging
+    pub fn info(&self) -> Option<PagingInfo> {
+        self.info
+    }
+
+    /// Push a group 7A.
+    ///
+    /// Returns the message once all of its groups are received, unless it repeats
+    /// the previous message.
+    #[cfg(feature = "unstable")]
+    pub fn push_group(
+        &mut self,
+        block2: &Block2,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<PagingMessage> {
+        self.messages.push_group(block2, block3, block4)
+    }
+
+    pub fn reset(&mut self) {
+        self.info = None;
+        #[cfg(feature = "unstable")]
+        self.messages.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paging_info() {
+        let mut decoder = PagingDecoder::new();
+        decoder.push_paging_codes(&Block2(0x1000));
+        assert_eq!(decoder.info(), None);
+        decoder.push_paging_codes(&Block2(0x1000 | (5 << 2) | 2));
+        decoder.push_operator_code(3);
+        let info = decoder.info().unwrap();
+        assert_eq!(info.network_group, 5);
+        assert_eq!(info.battery_saving_interval, 2);
+        assert_eq!(info.operator_code, Some(3));
+        assert_eq!(info.area_code, None);
+    }
+
+    #[test]
+    fn test_paging_identification() {
+        let mut decoder = PagingDecoder::new();
+        // Slow labelling variant 2: OPC 5, PAC 42
+        decoder.push_paging_identification((5 << 8) | 42);
+        let info = decoder.info().unwrap();
+        assert_eq!(info.operator_code, Some(5));
+        assert_eq!(info.area_code, Some(42));
+    }
+}
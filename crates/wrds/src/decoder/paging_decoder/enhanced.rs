@@ -0,0 +1,60 @@
+//! Enhanced paging system information in group 13A.
+//!
+//! The layout below was not checked against the enhanced paging specification,
+//! which is why it is only built with the `unstable` feature:
+//! - Block 2: the sub type in bits 4 to 2, the cycle selection in bits 1 and 0
+//! - Block 3: the interval numbering in bits 15 to 12, the batch in bits 11 to 8
+//!
+//! The other sub types are reserved for address notification and are ignored.
+
+use crate::types::{Block2, Block3, EnhancedPaging};
+
+/// Sub type in Block 2 of group 13A
+const SUB_TYPE_SHIFT: usize = 2;
+const SUB_TYPE_BITMASK: u16 = 0x7;
+
+/// Sub type of group 13A carrying the enhanced paging system information
+const SUB_TYPE_SYSTEM_INFORMATION: u16 = 0;
+
+/// Cycle selection in Block 2 of group 13A
+const CYCLE_BITMASK: u16 = 0x3;
+
+/// Interval numbering in Block 3 of group 13A
+const INTERVAL_SHIFT: usize = 12;
+const INTERVAL_BITMASK: u16 = 0xF;
+
+/// Batch number in Block 3 of group 13A
+const BATCH_SHIFT: usize = 8;
+const BATCH_BITMASK: u16 = 0xF;
+
+/// Returns the system information of a group 13A, or `None` for the other sub types
+pub fn system_information(block2: &Block2, block3: &Block3) -> Option<EnhancedPaging> {
+    if (block2.0 >> SUB_TYPE_SHIFT) & SUB_TYPE_BITMASK != SUB_TYPE_SYSTEM_INFORMATION {
+        return None;
+    }
+    Some(EnhancedPaging {
+        cycle: (block2.0 & CYCLE_BITMASK) as u8,
+        interval: ((block3.0 >> INTERVAL_SHIFT) & INTERVAL_BITMASK) as u8,
+        batch: ((block3.0 >> BATCH_SHIFT) & BATCH_BITMASK) as u8,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_information() {
+        // Sub type 0: cycle 2, interval 9, batch 3
+        assert_eq!(
+            system_information(&Block2(0xD002), &Block3(0x9300)),
+            Some(EnhancedPaging {
+                cycle: 2,
+                interval: 9,
+                batch: 3,
+            })
+        );
+        // Other sub types are ignored
+        assert_eq!(system_information(&Block2(0xD004), &Block3(0xFF00)), None);
+    }
+}
@@ -0,0 +1,266 @@
+//! Radio paging messages in group 7A.
+//!
+//! The layout below was not checked against the radio paging annex of IEC 62106 /
+//! EN 50067 nor against captured paging traffic, which is why it is only built
+//! with the `unstable` feature.
+//!
+//! Group 7A carries a paging message in up to 16 groups. Block 2 holds the
+//! A/B flag, which toggles between messages, and the paging segment address.
+//!
+//! The first group (segment address 0) addresses the pager:
+//! - Block 3: Y1 Y2 Z1 Z2, the group code and first digits of the individual code (BCD)
+//! - Block 4: Z3 Z4 (BCD), followed by the message type in the upper 4 bits
+//!   (0: beep only, 1: numeric, 2: alphanumeric) and the number of following
+//!   groups in the lower 4 bits
+//!
+//! Each following group carries 8 BCD digits or 4 characters in Blocks 3 and 4.
+
+use crate::{
+    decoder::{rds_charset::to_basic_rds_char, segment_buffer::SegmentBuffer},
+    types::{
+        Block2, Block3, Block4, PagerAddress, PagingContent, PagingDigitString, PagingMessage,
+        PagingTextString, MAX_PAGING_SEGMENTS,
+    },
+};
+
+/// A/B flag in Block 2 of group 7A
+const FLAG_BITMASK: u16 = 0x10;
+
+/// Paging segment address in Block 2 of group 7A
+const SEGMENT_BITMASK: u16 = 0x0F;
+
+/// Message types of the first group
+const TYPE_BEEP: u8 = 0;
+const TYPE_NUMERIC: u8 = 1;
+const TYPE_ALPHANUMERIC: u8 = 2;
+
+/// Carriage return ending alphanumeric messages
+const END_OF_TEXT: u8 = 0x0D;
+
+/// Number of bytes carried by Blocks 3 and 4 of each following group
+const SEGMENT_SIZE: usize = 4;
+
+/// Bytes of the groups following the first group of a message
+type PagingSegments = SegmentBuffer<{ MAX_PAGING_SEGMENTS * SEGMENT_SIZE }, MAX_PAGING_SEGMENTS>;
+
+/// Paging message that is being reassembled
+#[derive(Debug)]
+struct Partial {
+    flag: bool,
+    pager: PagerAddress,
+    message_type: u8,
+    segment_count: usize,
+    segments: PagingSegments,
+}
+
+/// Reassembles the paging messages of group 7A
+#[derive(Debug)]
+pub struct MessageDecoder {
+    partial: Option<Partial>,
+    /// Last message, used to drop repetitions
+    last_message: Option<PagingMessage>,
+}
+
+impl MessageDecoder {
+    pub fn new() -> Self {
+        Self {
+            partial: None,
+            last_message: None,
+        }
+    }
+
+    /// Push a group 7A.
+    ///
+    /// Returns the message once all of its groups are received, unless it repeats
+    /// the previous message.
+    pub fn push_group(
+        &mut self,
+        block2: &Block2,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<PagingMessage> {
+        let flag = block2.0 & FLAG_BITMASK != 0;
+        let segment = usize::from(block2.0 & SEGMENT_BITMASK);
+
+        if segment == 0 {
+            self.partial = None;
+            let [z3z4, format] = block4.0.to_be_bytes();
+            let pager = pager_address(block3.0, z3z4)?;
+            let partial = Partial {
+                flag,
+                pager,
+                message_type: format >> 4,
+                segment_count: usize::from(format & 0x0F),
+                segments: SegmentBuffer::new(0),
+            };
+            if partial.message_type == TYPE_BEEP || partial.segment_count == 0 {
+                return self.complete(&partial);
+            }
+            self.partial = Some(partial);
+            return None;
+        }
+
+        let partial = self.partial.as_mut()?;
+        if partial.flag != flag {
+            self.partial = None;
+            return None;
+        }
+        if segment > partial.segment_count {
+            return None;
+        }
+        let [byte1, byte2] = block3.0.to_be_bytes();
+        let [byte3, byte4] = block4.0.to_be_bytes();
+        partial
+            .segments
+            .push(segment - 1, &[byte1, byte2, byte3, byte4]);
+
+        if !partial.segments.first_received(partial.segment_count) {
+            return None;
+        }
+        let partial = self.partial.take()?;
+        self.complete(&partial)
+    }
+
+    pub fn reset(&mut self) {
+        self.partial = None;
+        self.last_message = None;
+    }
+
+    fn complete(&mut self, partial: &Partial) -> Option<PagingMessage> {
+        let segments = &partial.segments.bytes()[..partial.segment_count * SEGMENT_SIZE];
+        let content = match partial.message_type {
+            TYPE_BEEP => PagingContent::Beep,
+            TYPE_NUMERIC => PagingContent::Numeric(numeric(segments)),
+            TYPE_ALPHANUMERIC => PagingContent::Alphanumeric(alphanumeric(segments)),
+            _ => return None,
+        };
+        let message = PagingMessage {
+            pager: partial.pager,
+            content,
+            network: None,
+        };
+        if self.last_message.as_ref() == Some(&message) {
+            return None;
+        }
+        self.last_message = Some(message.clone());
+        Some(message)
+    }
+}
+
+/// Decode the pager address from the BCD digits Y1 Y2 Z1 Z2 and Z3 Z4.
+///
+/// Returns `None` if any digit is not decimal.
+fn pager_address(y1y2z1z2: u16, z3z4: u8) -> Option<PagerAddress> {
+    let digits = (u32::from(y1y2z1z2) << 8) | u32::from(z3z4);
+    let value = (0..6).rev().try_fold(0u32, |value, index| {
+        let digit = (digits >> (index * 4)) & 0xF;
+        (digit <= 9).then_some(value * 10 + digit)
+    })?;
+    Some(PagerAddress {
+        group: (value / 10_000) as u8,
+        individual: (value % 10_000) as u16,
+    })
+}
+
+/// Decode the BCD digits of a numeric message, which end at the first non-decimal digit
+fn numeric(bytes: &[u8]) -> PagingDigitString {
+    let mut digits = PagingDigitString::new();
+    let nibbles = bytes.iter().flat_map(|byte| [byte >> 4, byte & 0xF]);
+    for nibble in nibbles {
+        if nibble > 9 {
+            break;
+        }
+        let _ = digits.push(char::from(b'0' + nibble));
+    }
+    digits
+}
+
+/// Decode the characters of an alphanumeric message, which end at a carriage return
+fn alphanumeric(bytes: &[u8]) -> PagingTextString {
+    let mut text = PagingTextString::new();
+    for &byte in bytes {
+        if byte == END_OF_TEXT {
+            break;
+        }
+        let _ = text.push(to_basic_rds_char(byte).unwrap_or(' '));
+    }
+    let trimmed = text.trim_end().len();
+    text.truncate(trimmed);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Group 7A with the A/B flag and segment address
+    fn block2(flag: bool, segment: u16) -> Block2 {
+        Block2(0x7000 | if flag { FLAG_BITMASK } else { 0 } | segment)
+    }
+
+    #[test]
+    fn test_beep() {
+        let mut decoder = MessageDecoder::new();
+        let message = decoder
+            .push_group(&block2(false, 0), &Block3(0x1234), &Block4(0x5600))
+            .unwrap();
+        assert_eq!(
+            message.pager,
+            PagerAddress {
+                group: 12,
+                individual: 3456
+            }
+        );
+        assert_eq!(message.content, PagingContent::Beep);
+        // Repetition is dropped
+        assert_eq!(
+            decoder.push_group(&block2(false, 0), &Block3(0x1234), &Block4(0x5600)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_numeric() {
+        let mut decoder = MessageDecoder::new();
+        decoder.push_group(&block2(true, 0), &Block3(0x0000), &Block4(0x0112));
+        assert_eq!(
+            decoder.push_group(&block2(true, 2), &Block3(0x9FFF), &Block4(0xFFFF)),
+            None
+        );
+        let message = decoder
+            .push_group(&block2(true, 1), &Block3(0x0123), &Block4(0x4567))
+            .unwrap();
+        assert_eq!(
+            message.content,
+            PagingContent::Numeric("012345679".try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_alphanumeric() {
+        let mut decoder = MessageDecoder::new();
+        decoder.push_group(&block2(false, 0), &Block3(0x0000), &Block4(0x0122));
+        decoder.push_group(&block2(false, 1), &Block3(0x4341), &Block4(0x4C4C));
+        let message = decoder
+            .push_group(&block2(false, 2), &Block3(0x204D), &Block4(0x450D))
+            .unwrap();
+        assert_eq!(
+            message.content,
+            PagingContent::Alphanumeric("CALL ME".try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_flag_change_discards_message() {
+        let mut decoder = MessageDecoder::new();
+        decoder.push_group(&block2(false, 0), &Block3(0x0000), &Block4(0x0111));
+        assert_eq!(
+            decoder.push_group(&block2(true, 1), &Block3(0x0123), &Block4(0x4567)),
+            None
+        );
+        assert_eq!(
+            decoder.push_group(&block2(false, 1), &Block3(0x0123), &Block4(0x4567)),
+            None
+        );
+    }
+}
@@ -1,15 +1,17 @@
 use core::fmt;
 
 use crate::{
-    decoder::{bitset::Bitset, rds_charset::to_basic_rds_char},
+    decoder::{
+        char_voter::CharVoter, rds_charset::RdsCharsetDecoder, segment_buffer::SegmentBuffer,
+    },
     types::{ProgrammeServiceName, ProgrammeServiceNameString},
 };
 
 /// Maximum of PS
 const PS_SIZE: usize = 8;
 
-/// Empty PS
-const EMPTY_PS: [char; PS_SIZE] = [' '; PS_SIZE];
+/// Number of segments in PS
+const NUM_SEGMENTS: usize = PS_SIZE / 2;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum PsDecoderError {
@@ -29,46 +31,60 @@ pub type Result<T> = core::result::Result<T, PsDecoderError>;
 /// Decoder for Programme Service Name (PS)
 #[derive(Debug)]
 pub struct PsDecoder {
-    segments: [char; PS_SIZE],
-    is_chars_set: Bitset<4>,
+    segments: SegmentBuffer<PS_SIZE, NUM_SEGMENTS>,
+    /// Per-character voting, if enabled
+    voter: Option<CharVoter<PS_SIZE>>,
 }
 
 impl PsDecoder {
     /// Creates new PsDecoder
     pub fn new() -> Self {
         Self {
-            segments: EMPTY_PS,
-            is_chars_set: Bitset::default(),
+            segments: SegmentBuffer::new(b' '),
+            voter: None,
         }
     }
 
+    /// Enable per-character voting requiring `min_votes` agreeing receptions, or
+    /// disable it if `None`
+    pub fn set_voting(&mut self, min_votes: Option<u8>) {
+        self.voter = min_votes.map(CharVoter::new);
+        self.reset();
+    }
+
     /// Push new PS segment
     ///
     /// Resets the segments if different segment is pushed when all segments are already set.
+    /// With voting, each character is only replaced once another one wins the vote.
     pub fn push_segment(&mut self, index: usize, segment_bytes: [u8; 2]) -> Result<()> {
-        if index >= (self.segments.len() / 2) {
+        if index >= NUM_SEGMENTS {
             return Err(PsDecoderError::IndexOutOfBounds(index));
         }
 
         let current_index1 = 2 * index;
         let current_index2 = (2 * index) + 1;
 
-        let char1 = to_basic_rds_char(segment_bytes[0]).unwrap_or(' ');
-        let char2 = to_basic_rds_char(segment_bytes[1]).unwrap_or(' ');
+        if let Some(voter) = &mut self.voter {
+            let mut is_confirmed = true;
+            for (position, byte) in (current_index1..=current_index2).zip(segment_bytes) {
+                match voter.push(position, byte) {
+                    Some(winner) => self.segments.set(position, winner),
+                    None => is_confirmed = false,
+                }
+            }
+            if is_confirmed {
+                self.segments.set_received(index);
+            }
+            return Ok(());
+        }
 
-        let incoming = [char1, char2];
-        let current = &self.segments[current_index1..=current_index2];
+        let current = &self.segments.bytes()[current_index1..=current_index2];
 
-        if self.is_chars_set.all() && current != incoming {
-            self.segments = EMPTY_PS;
-            self.is_chars_set.reset();
+        if self.segments.is_complete() && current != segment_bytes {
+            self.segments.reset();
         }
 
-        self.segments[current_index1] = char1;
-        self.segments[current_index2] = char2;
-        self.is_chars_set
-            .set_bit(index)
-            .expect("The index should always be valid");
+        self.segments.push(index, &segment_bytes);
         Ok(())
     }
 
@@ -77,27 +93,31 @@ impl PsDecoder {
     /// - If ready, returns PS represented as bytes.
     /// - If not, returns `None`.
     pub fn confirmed(&self) -> Option<ProgrammeServiceName> {
-        if !self.is_chars_set.all() {
+        if !self.segments.is_complete() {
             return None;
         }
-        let ps = ProgrammeServiceNameString::from_iter(self.segments.iter());
-        Some(ProgrammeServiceName::new(ps))
+        let mut charset = RdsCharsetDecoder::new();
+        let ps = ProgrammeServiceNameString::from_iter(charset.decode(self.segments.bytes()));
+        Some(ProgrammeServiceName::new(ps).with_code_table(charset.code_table()))
     }
 
     pub fn reset(&mut self) {
-        self.segments = EMPTY_PS;
-        self.is_chars_set.reset();
+        self.segments.reset();
+        if let Some(voter) = &mut self.voter {
+            voter.reset();
+        }
     }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::RdsCodeTable;
 
     #[test]
     fn test_new_decoder_is_empty() {
         let decoder = PsDecoder::new();
-        assert_eq!(decoder.segments, EMPTY_PS);
-        assert!(!decoder.is_chars_set.all());
+        assert_eq!(decoder.segments.bytes(), b"        ");
+        assert!(!decoder.segments.is_complete());
         assert_eq!(decoder.confirmed(), None);
     }
 
@@ -105,9 +125,9 @@ mod tests {
     fn test_push_segment_sets_segments() {
         let mut decoder = PsDecoder::new();
         decoder.push_segment(0, [b'A', b'B']).unwrap();
-        assert_eq!(decoder.segments[0], 'A');
-        assert_eq!(decoder.segments[1], 'B');
-        assert!(!decoder.is_chars_set.all());
+        assert_eq!(decoder.segments.bytes()[0], b'A');
+        assert_eq!(decoder.segments.bytes()[1], b'B');
+        assert!(!decoder.segments.is_complete());
         assert_eq!(decoder.confirmed(), None);
     }
 
@@ -118,7 +138,7 @@ mod tests {
         for (i, pair) in chars.iter().enumerate() {
             decoder.push_segment(i, *pair).unwrap();
         }
-        assert!(decoder.is_chars_set.all());
+        assert!(decoder.segments.is_complete());
         let expected = {
             let ps = String::from("ABCDEFGH");
             ProgrammeServiceName::new(ProgrammeServiceNameString::from_iter(ps.chars()))
@@ -132,12 +152,12 @@ mod tests {
         for i in 0..4 {
             decoder.push_segment(i, [b'X', b'Y']).unwrap();
         }
-        assert!(decoder.is_chars_set.all());
+        assert!(decoder.segments.is_complete());
         // Next push should reset
         decoder.push_segment(0, [b'A', b'B']).unwrap();
-        assert_eq!(decoder.segments[0], 'A');
-        assert_eq!(decoder.segments[1], 'B');
-        assert!(!decoder.is_chars_set.all());
+        assert_eq!(decoder.segments.bytes()[0], b'A');
+        assert_eq!(decoder.segments.bytes()[1], b'B');
+        assert!(!decoder.segments.is_complete());
         assert_eq!(decoder.confirmed(), None);
     }
 
@@ -148,8 +168,8 @@ mod tests {
             decoder.push_segment(i, [b'X', b'Y']).unwrap();
         }
         decoder.reset();
-        assert_eq!(decoder.segments, EMPTY_PS);
-        assert!(!decoder.is_chars_set.all());
+        assert_eq!(decoder.segments.bytes(), b"        ");
+        assert!(!decoder.segments.is_complete());
         assert_eq!(decoder.confirmed(), None);
     }
 
@@ -169,7 +189,7 @@ mod tests {
         for (i, pair) in chars.iter().enumerate() {
             decoder.push_segment(i, *pair).unwrap();
         }
-        assert!(decoder.is_chars_set.all());
+        assert!(decoder.segments.is_complete());
         // Next push should not reset
         decoder.push_segment(0, [b'A', b'B']).unwrap();
         let expected = {
@@ -184,14 +204,15 @@ mod tests {
         let mut decoder = PsDecoder::new();
         assert_eq!(decoder.push_segment(0, [0x1F, b'B']), Ok(()));
         assert_eq!(decoder.push_segment(1, [b'A', 0x01]), Ok(()));
-        assert_eq!(decoder.segments[0], ' ');
-        assert_eq!(decoder.segments[3], ' ');
+        assert_eq!(decoder.push_segment(2, [b'C', b'D']), Ok(()));
+        assert_eq!(decoder.push_segment(3, [b'E', b'F']), Ok(()));
+        assert_eq!(decoder.confirmed().unwrap().as_str(), " BA CDEF");
     }
 
     #[test]
     fn test_non_ascii_full_string() {
         let mut decoder = PsDecoder::new();
-        for i in 0..(decoder.segments.len() / 2) {
+        for i in 0..NUM_SEGMENTS {
             let _ = decoder.push_segment(i, [0xAE; 2]);
         }
         let expected = {
@@ -201,4 +222,36 @@ mod tests {
         };
         assert_eq!(decoder.confirmed(), Some(expected));
     }
+
+    #[test]
+    fn test_code_table_switching() {
+        let mut decoder = PsDecoder::new();
+        // SO SO selects the G1 code table, whose upper half is not transcribed
+        let chars = [[0x0E, 0x0E], [b'R', b'A'], [b'D', 0xC9], [b'O', b' ']];
+        for (i, pair) in chars.iter().enumerate() {
+            decoder.push_segment(i, *pair).unwrap();
+        }
+        let ps = decoder.confirmed().unwrap();
+        assert_eq!(ps.as_str(), "RAD O ");
+        assert_eq!(ps.code_table(), RdsCodeTable::G1);
+    }
+
+    #[test]
+    fn test_voting() {
+        let mut decoder = PsDecoder::new();
+        decoder.set_voting(Some(2));
+        let chars = [[b'A', b'B'], [b'C', b'D'], [b'E', b'F'], [b'G', b'H']];
+        for (i, pair) in chars.iter().enumerate() {
+            decoder.push_segment(i, *pair).unwrap();
+        }
+        assert_eq!(decoder.confirmed(), None);
+        for (i, pair) in chars.iter().enumerate() {
+            decoder.push_segment(i, *pair).unwrap();
+        }
+        assert_eq!(decoder.confirmed().unwrap().as_str(), "ABCDEFGH");
+
+        // A corrupted segment neither resets nor changes the PS
+        decoder.push_segment(1, [b'X', b'D']).unwrap();
+        assert_eq!(decoder.confirmed().unwrap().as_str(), "ABCDEFGH");
+    }
 }
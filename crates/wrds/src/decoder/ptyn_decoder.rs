@@ -0,0 +1,83 @@
+use crate::{
+    decoder::{rds_charset::RdsCharsetDecoder, segment_buffer::SegmentBuffer},
+    types::{ProgrammeTypeName, ProgrammeTypeNameString, PTYN_LENGTH},
+};
+
+/// Number of segments in the PTYN
+const NUM_SEGMENTS: usize = 2;
+
+/// Number of characters in each segment of the PTYN
+const SEGMENT_SIZE: usize = PTYN_LENGTH / NUM_SEGMENTS;
+
+/// Decoder for Programme Type Name (PTYN) from group 10A
+#[derive(Debug)]
+pub struct PtynDecoder {
+    segments: SegmentBuffer<PTYN_LENGTH, NUM_SEGMENTS>,
+    text_ab: Option<bool>,
+}
+
+impl PtynDecoder {
+    pub fn new() -> Self {
+        Self {
+            segments: SegmentBuffer::new(b' '),
+            text_ab: None,
+        }
+    }
+
+    /// Push a PTYN segment, clearing the PTYN when the A/B flag toggles
+    pub fn push_segment(&mut self, index: usize, chars: [u8; SEGMENT_SIZE], text_ab: bool) {
+        let index = index % NUM_SEGMENTS;
+        if self.text_ab != Some(text_ab) {
+            self.reset();
+            self.text_ab = Some(text_ab);
+        }
+        self.segments.push(index, &chars);
+    }
+
+    /// Returns the PTYN once both segments are received
+    pub fn confirmed(&self) -> Option<ProgrammeTypeName> {
+        if !self.segments.is_complete() {
+            return None;
+        }
+        let mut charset = RdsCharsetDecoder::new();
+        let ptyn = ProgrammeTypeNameString::from_iter(charset.decode(self.segments.bytes()));
+        Some(ProgrammeTypeName::new(ptyn).with_code_table(charset.code_table()))
+    }
+
+    pub fn reset(&mut self) {
+        self.segments.reset();
+        self.text_ab = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RdsCodeTable;
+
+    #[test]
+    fn test_confirmed() {
+        let mut decoder = PtynDecoder::new();
+        decoder.push_segment(0, *b"Foot", false);
+        assert_eq!(decoder.confirmed(), None);
+        decoder.push_segment(1, *b"ball", false);
+        let ptyn = decoder.confirmed().unwrap();
+        assert_eq!(ptyn.as_str(), "Football");
+        assert_eq!(ptyn.code_table(), RdsCodeTable::G0);
+
+        // The A/B flag toggles for a new PTYN
+        decoder.push_segment(0, *b"Rock", true);
+        assert_eq!(decoder.confirmed(), None);
+    }
+
+    #[test]
+    fn test_code_table_switching() {
+        let mut decoder = PtynDecoder::new();
+        // ESC o selects the G3 code table
+        decoder.push_segment(0, [0x1B, 0x6F, b'A', 0xA1], false);
+        decoder.push_segment(1, [b'B', b' ', b' ', b' '], false);
+        let ptyn = decoder.confirmed().unwrap();
+        assert_eq!(ptyn.as_str(), "A B   ");
+        assert_eq!(ptyn.code_table(), RdsCodeTable::G3);
+    }
+}
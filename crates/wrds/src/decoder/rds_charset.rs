@@ -1,14 +1,18 @@
 use core::{error::Error, fmt};
 
+use crate::types::RdsCodeTable;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RdsCharError {
     InvalidRdsChar(u8),
+    UnencodableChar(char),
 }
 
 impl fmt::Display for RdsCharError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             RdsCharError::InvalidRdsChar(c) => write!(f, "Invalid RDS character {c}"),
+            RdsCharError::UnencodableChar(c) => write!(f, "Unencodable RDS character {c}"),
         }
     }
 }
@@ -17,7 +21,7 @@ impl Error for RdsCharError {}
 
 pub type Result<T> = core::result::Result<T, RdsCharError>;
 
-pub fn to_basic_rds_char(byte: u8) -> Result<char> {
+pub const fn to_basic_rds_char(byte: u8) -> Result<char> {
     /// Minimum value for RDS printable char
     const PRINTABLE_MIN: u8 = 0x20;
     /// Max value for RDS printable char
@@ -168,7 +172,7 @@ pub fn to_basic_rds_char(byte: u8) -> Result<char> {
     const SMALL_T_STROKE: u8 = 0xFE;
 
     match byte {
-        LINE_FEED | CARRIAGE_RETURN => Ok(char::from(byte)),
+        LINE_FEED | CARRIAGE_RETURN => Ok(byte as char),
         CURRENCY_SIGN => Ok('¤'),
         HORIZONTAL_BAR => Ok('―'),
         DOUBLE_VERTICAL_LINE => Ok('║'),
@@ -301,11 +305,253 @@ pub fn to_basic_rds_char(byte: u8) -> Result<char> {
         SMALL_S_ACUTE => Ok('ś'),
         SMALL_Z_ACUTE => Ok('ź'),
         SMALL_T_STROKE => Ok('ŧ'),
-        PRINTABLE_MIN..=PRINTABLE_MAX => Ok(char::from(byte)),
+        PRINTABLE_MIN..=PRINTABLE_MAX => Ok(byte as char),
         _ => Err(RdsCharError::InvalidRdsChar(byte)),
     }
 }
 
+/// Shift In (SI), sent twice to select the G0 code table
+const SHIFT_IN: u8 = 0x0F;
+/// Shift Out (SO), sent twice to select the G1 code table
+const SHIFT_OUT: u8 = 0x0E;
+/// Escape (ESC), followed by `n` or `o` to select the G2 or G3 code table
+const ESCAPE: u8 = 0x1B;
+const ESCAPE_G2: u8 = 0x6E;
+const ESCAPE_G3: u8 = 0x6F;
+
+/// Lower half shared by every code table
+const LOWER_HALF_MAX: u8 = 0x7F;
+
+/// Converts an RDS character using the code table.
+///
+/// Only the G0 table is transcribed, so the upper halves of the G1, G2 and G3
+/// tables are reported as invalid characters.
+pub const fn to_rds_char(byte: u8, table: RdsCodeTable) -> Result<char> {
+    match (table, byte) {
+        (RdsCodeTable::G0, _) | (_, ..=LOWER_HALF_MAX) => to_basic_rds_char(byte),
+        _ => Err(RdsCharError::InvalidRdsChar(byte)),
+    }
+}
+
+/// Stateful decoder of RDS text honouring the code table switching sequences.
+///
+/// Text starts in the G0 code table. The two character sequences SI SI, SO SO,
+/// ESC `n` and ESC `o` select the G0, G1, G2 and G3 code tables for the rest of the
+/// text, and are not displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RdsCharsetDecoder {
+    table: RdsCodeTable,
+    pending: Option<u8>,
+}
+
+impl RdsCharsetDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the active code table
+    pub fn code_table(&self) -> RdsCodeTable {
+        self.table
+    }
+
+    /// Push the next byte of the text, returning `None` for the bytes of the
+    /// switching sequences
+    pub fn push(&mut self, byte: u8) -> Option<Result<char>> {
+        if let Some(first) = self.pending.take() {
+            let table = match (first, byte) {
+                (SHIFT_IN, SHIFT_IN) => Some(RdsCodeTable::G0),
+                (SHIFT_OUT, SHIFT_OUT) => Some(RdsCodeTable::G1),
+                (ESCAPE, ESCAPE_G2) => Some(RdsCodeTable::G2),
+                (ESCAPE, ESCAPE_G3) => Some(RdsCodeTable::G3),
+                _ => None,
+            };
+            if let Some(table) = table {
+                self.table = table;
+                return None;
+            }
+        }
+        match byte {
+            SHIFT_IN | SHIFT_OUT | ESCAPE => {
+                self.pending = Some(byte);
+                None
+            }
+            _ => Some(to_rds_char(byte, self.table)),
+        }
+    }
+
+    /// Decode the whole text from the G0 code table, replacing invalid characters
+    /// with spaces
+    pub fn decode<'a>(&'a mut self, bytes: &'a [u8]) -> impl Iterator<Item = char> + 'a {
+        self.reset();
+        bytes
+            .iter()
+            .filter_map(|byte| self.push(*byte))
+            .map(|c| c.unwrap_or(' '))
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Default fallback for characters that cannot be encoded
+const DEFAULT_FALLBACK: u8 = b'?';
+
+/// Similar characters used for the characters missing from the G0 code table
+const TRANSLITERATIONS: [(char, char); 36] = [
+    ('‘', '\''),
+    ('’', '\''),
+    ('‚', '\''),
+    ('`', '\''),
+    ('“', '"'),
+    ('”', '"'),
+    ('„', '"'),
+    ('«', '"'),
+    ('»', '"'),
+    ('–', '-'),
+    ('—', '-'),
+    ('~', '-'),
+    ('…', '.'),
+    ('ą', 'a'),
+    ('Ą', 'A'),
+    ('ă', 'a'),
+    ('Ă', 'A'),
+    ('ď', 'd'),
+    ('Ď', 'D'),
+    ('ę', 'e'),
+    ('Ę', 'E'),
+    ('ė', 'e'),
+    ('ł', 'l'),
+    ('Ł', 'L'),
+    ('ľ', 'l'),
+    ('Ľ', 'L'),
+    ('ș', 's'),
+    ('Ș', 'S'),
+    ('ț', 't'),
+    ('Ț', 'T'),
+    ('ť', 't'),
+    ('Ť', 'T'),
+    ('ů', 'u'),
+    ('Ů', 'U'),
+    ('ż', 'z'),
+    ('Ż', 'Z'),
+];
+
+/// Encoder of text into RDS characters, the reverse of [`RdsCharsetDecoder`].
+///
+/// Text is encoded with the G0 code table only, as the other code tables are not
+/// transcribed, see [`RdsCodeTable`]. Characters missing from the G0 table are
+/// transliterated to a similar character when possible, and otherwise replaced by
+/// the fallback character or dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RdsCharsetEncoder {
+    fallback: Option<u8>,
+}
+
+impl Default for RdsCharsetEncoder {
+    fn default() -> Self {
+        Self {
+            fallback: Some(DEFAULT_FALLBACK),
+        }
+    }
+}
+
+impl RdsCharsetEncoder {
+    /// Creates an encoder replacing the characters that cannot be encoded with `?`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an encoder replacing the characters that cannot be encoded with
+    /// `fallback`, or dropping them if `None`.
+    ///
+    /// # Errors
+    /// Returns an error if the fallback is not in the G0 code table.
+    pub fn with_fallback(fallback: Option<char>) -> Result<Self> {
+        let fallback = match fallback {
+            Some(c) => Some(find_byte(c).ok_or(RdsCharError::UnencodableChar(c))?),
+            None => None,
+        };
+        Ok(Self { fallback })
+    }
+
+    /// Encode the text into RDS characters of the G0 code table
+    pub fn encode<'a>(&self, text: &'a str) -> impl Iterator<Item = u8> + 'a {
+        let fallback = self.fallback;
+        text.chars()
+            .filter_map(move |c| encode_char(c).or(fallback))
+    }
+}
+
+/// Returns true if the G0 code table decodes the byte as the ASCII character of
+/// the same value
+const fn is_ascii_identity(byte: u8) -> bool {
+    match to_basic_rds_char(byte) {
+        Ok(c) => byte.is_ascii() && c as u32 == byte as u32,
+        Err(_) => false,
+    }
+}
+
+/// Number of characters of the G0 code table that are not decoded as ASCII
+const REVERSE_TABLE_LEN: usize = {
+    let mut len = 0;
+    let mut byte = 0;
+    while byte <= u8::MAX as usize {
+        if !is_ascii_identity(byte as u8) && to_basic_rds_char(byte as u8).is_ok() {
+            len += 1;
+        }
+        byte += 1;
+    }
+    len
+};
+
+/// Characters of the G0 code table that are not decoded as ASCII, sorted by
+/// character for the reverse lookup of the encoder
+static REVERSE_TABLE: [(char, u8); REVERSE_TABLE_LEN] = {
+    let mut entries = [('\0', 0); REVERSE_TABLE_LEN];
+    let mut len = 0;
+    let mut byte = 0;
+    while byte <= u8::MAX as usize {
+        if let (false, Ok(c)) = (is_ascii_identity(byte as u8), to_basic_rds_char(byte as u8)) {
+            // Insertion sort, keeping the smallest byte first for characters found
+            // at several positions
+            let mut index = len;
+            while index > 0 && entries[index - 1].0 > c {
+                entries[index] = entries[index - 1];
+                index -= 1;
+            }
+            entries[index] = (c, byte as u8);
+            len += 1;
+        }
+        byte += 1;
+    }
+    entries
+};
+
+/// Returns the byte of the character in the G0 code table
+fn find_byte(c: char) -> Option<u8> {
+    if let Ok(byte) = u8::try_from(c) {
+        if is_ascii_identity(byte) {
+            return Some(byte);
+        }
+    }
+    let index = REVERSE_TABLE.partition_point(|(entry, _)| *entry < c);
+    match REVERSE_TABLE.get(index) {
+        Some((entry, byte)) if *entry == c => Some(*byte),
+        _ => None,
+    }
+}
+
+/// Encode a character, or its transliteration if it is missing from the G0 code table
+fn encode_char(c: char) -> Option<u8> {
+    find_byte(c).or_else(|| {
+        TRANSLITERATIONS
+            .iter()
+            .find(|(from, _)| *from == c)
+            .and_then(|(_, to)| find_byte(*to))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,4 +617,108 @@ mod tests {
         assert_eq!(to_basic_rds_char(0xFD), Ok('ź'));
         assert_eq!(to_basic_rds_char(0xFE), Ok('ŧ'));
     }
+
+    #[test]
+    fn test_code_tables() {
+        assert_eq!(to_rds_char(0xC1, RdsCodeTable::G0), Ok('À'));
+        // The lower half is shared by every table
+        assert_eq!(to_rds_char(b'A', RdsCodeTable::G2), Ok('A'));
+        assert_eq!(to_rds_char(0x24, RdsCodeTable::G1), Ok('¤'));
+        // The upper halves of G1 to G3 are not transcribed
+        for table in [RdsCodeTable::G1, RdsCodeTable::G2, RdsCodeTable::G3] {
+            assert_eq!(
+                to_rds_char(0xC1, table),
+                Err(RdsCharError::InvalidRdsChar(0xC1))
+            );
+        }
+    }
+
+    #[test]
+    fn test_switching_sequences() {
+        let mut decoder = RdsCharsetDecoder::new();
+        let bytes = [
+            b'A', 0x0E, 0x0E, b'B', 0xC1, 0x1B, 0x6F, b'C', 0x0F, 0x0F, 0xC1,
+        ];
+        let text: String = decoder.decode(&bytes).collect();
+        assert_eq!(text, "AB CÀ");
+        assert_eq!(decoder.code_table(), RdsCodeTable::G0);
+
+        // A lone control character is dropped without switching
+        let text: String = decoder.decode(&[0x0E, b'B', 0xC1]).collect();
+        assert_eq!(text, "BÀ");
+        assert_eq!(decoder.code_table(), RdsCodeTable::G0);
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let encoder = RdsCharsetEncoder::new();
+        let text = "Zürich àé ß ŧ";
+        let bytes: Vec<u8> = encoder.encode(text).collect();
+        let decoded: String = RdsCharsetDecoder::new().decode(&bytes).collect();
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn test_encode_transliteration_and_fallback() {
+        let encoder = RdsCharsetEncoder::new();
+        let bytes: Vec<u8> = encoder.encode("Łódź “FM” 日").collect();
+        let decoded: String = RdsCharsetDecoder::new().decode(&bytes).collect();
+        assert_eq!(decoded, "Lódź \"FM\" ?");
+
+        let encoder = RdsCharsetEncoder::with_fallback(None).unwrap();
+        assert_eq!(encoder.encode("A日B").collect::<Vec<u8>>(), b"AB");
+        let encoder = RdsCharsetEncoder::with_fallback(Some('_')).unwrap();
+        assert_eq!(encoder.encode("A日").collect::<Vec<u8>>(), b"A_");
+        let encoder = RdsCharsetEncoder::with_fallback(Some('é')).unwrap();
+        assert_eq!(encoder.encode("A日").collect::<Vec<u8>>(), [b'A', 0x82]);
+        assert_eq!(
+            RdsCharsetEncoder::with_fallback(Some('日')),
+            Err(RdsCharError::UnencodableChar('日'))
+        );
+    }
+
+    #[test]
+    fn test_encode_without_switching() {
+        // Characters of the other code tables are not encoded until they are transcribed
+        let encoder = RdsCharsetEncoder::new();
+        assert_eq!(encoder.encode("AΩБא").collect::<Vec<u8>>(), b"A???");
+    }
+
+    #[test]
+    fn test_encode_g0_bytes() {
+        // Known positions of the G0 table, including ¤ at 0x24 and $ at 0xAB
+        let encoder = RdsCharsetEncoder::new();
+        let bytes: Vec<u8> = encoder.encode("é ß ç → Ñ ¤ $ €").collect();
+        assert_eq!(
+            bytes,
+            [
+                0x82, b' ', 0x8D, b' ', 0x9B, b' ', 0xAE, b' ', 0x8A, b' ', 0x24, b' ', 0xAB, b' ',
+                0xA9
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reverse_lookup() {
+        // The reverse lookup finds the first byte decoded as each character
+        for byte in 0..=u8::MAX {
+            if let Ok(c) = to_basic_rds_char(byte) {
+                let first = (0..=u8::MAX).find(|byte| to_basic_rds_char(*byte) == Ok(c));
+                assert_eq!(find_byte(c), first, "{c}");
+            }
+        }
+        assert_eq!(find_byte('日'), None);
+        // Only the characters that are not decoded as ASCII are in the table
+        assert!(REVERSE_TABLE
+            .iter()
+            .all(|(_, byte)| !is_ascii_identity(*byte)));
+        assert_eq!(REVERSE_TABLE_LEN, 131);
+    }
+
+    #[test]
+    fn test_transliterations_are_encodable() {
+        for (_, to) in TRANSLITERATIONS {
+            assert!(find_byte(to).is_some(), "{to}");
+        }
+    }
 }
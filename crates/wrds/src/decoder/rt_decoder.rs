@@ -1,21 +1,15 @@
-use crate::decoder::{bitset::Bitset, rds_charset::to_basic_rds_char};
+use crate::decoder::{
+    char_voter::CharVoter, rds_charset::RdsCharsetDecoder, segment_buffer::SegmentBuffer,
+};
 use crate::types::{
     RadioText, RadioTextPlusList, RadioTextPlusTag, RadioTextString, MAX_RT_LENGTH,
 };
 
-/// Empty RadioText Group A message
-const EMPTY_RT: [char; MAX_RT_LENGTH] = [' '; MAX_RT_LENGTH];
-
 /// Carriage return character in RadioText
 ///
 /// This is used to indicate the end of a RadioText message for messages that are
 /// for messages that require less than 16 segments addresses to transfer.
-const EARLY_RETURN: char = '\r';
-
-/// Space character in RadioText
-///
-/// Used to replace invalid characters and serve as padding.
-const SPACE: char = ' ';
+const EARLY_RETURN: u8 = 0x0D;
 
 /// Number of segments in RadioText
 const NUM_SEGMENTS: usize = 16;
@@ -38,28 +32,36 @@ enum Group {
 
 #[derive(Debug)]
 pub struct RtDecoder {
-    buffer: [char; MAX_RT_LENGTH],
+    buffer: SegmentBuffer<MAX_RT_LENGTH, NUM_SEGMENTS>,
     current_group: Option<Group>,
     text_ab: Option<bool>,
-    received_segments: Bitset<NUM_SEGMENTS>,
     early_idx: Option<usize>,
     rt_tag1: Option<RadioTextPlusTag>,
     rt_tag2: Option<RadioTextPlusTag>,
+    /// Per-character voting, if enabled
+    voter: Option<CharVoter<MAX_RT_LENGTH>>,
 }
 
 impl RtDecoder {
     pub fn new() -> Self {
         Self {
-            buffer: EMPTY_RT,
+            buffer: SegmentBuffer::new(b' '),
             current_group: None,
             text_ab: None,
-            received_segments: Bitset::default(),
             early_idx: None,
             rt_tag1: None,
             rt_tag2: None,
+            voter: None,
         }
     }
 
+    /// Enable per-character voting requiring `min_votes` agreeing receptions, or
+    /// disable it if `None`
+    pub fn set_voting(&mut self, min_votes: Option<u8>) {
+        self.voter = min_votes.map(CharVoter::new);
+        self.reset();
+    }
+
     pub fn push_segment_a(&mut self, index: usize, chars: [u8; 4], text_ab: bool) {
         self.push_segment(index, chars, text_ab, Group::A);
     }
@@ -82,21 +84,15 @@ impl RtDecoder {
         };
         // Check if all required segments are received
         let required_segments = length / segment_size;
-        let required_bitmask: u32 = (1 << required_segments) - 1;
-        let received_bitmask: u32 = {
-            let value: u32 = self.received_segments.value().into();
-            value & required_bitmask
-        };
-        if received_bitmask == required_bitmask {
-            let rt_string = RadioTextString::from_iter(&self.buffer[..length]);
-            let rt_plus = {
-                if self.rt_tag1.is_some() && self.rt_tag2.is_some() {
-                    RadioTextPlusList::from_array([self.rt_tag1.unwrap(), self.rt_tag2.unwrap()])
-                } else {
-                    RadioTextPlusList::new()
-                }
+        if self.buffer.first_received(required_segments) {
+            let mut charset = RdsCharsetDecoder::new();
+            let rt_string =
+                RadioTextString::from_iter(charset.decode(&self.buffer.bytes()[..length]));
+            let rt_plus = match (self.rt_tag1, self.rt_tag2) {
+                (Some(tag1), Some(tag2)) => RadioTextPlusList::from_array([tag1, tag2]),
+                _ => RadioTextPlusList::new(),
             };
-            return Some(RadioText::new(rt_string, rt_plus));
+            return Some(RadioText::new(rt_string, rt_plus).with_code_table(charset.code_table()));
         }
         None
     }
@@ -120,10 +116,20 @@ impl RtDecoder {
     }
 
     fn write_chars_to_buffer<const N: usize>(&mut self, segment_idx: usize, chars: &[u8; N]) {
+        let mut is_confirmed = true;
         for (char_idx, letter) in chars.iter().enumerate() {
             let letter_idx = N * segment_idx + char_idx;
-            let rt_char = to_basic_rds_char(*letter).unwrap_or(SPACE);
-            if rt_char == EARLY_RETURN {
+            let letter = match &mut self.voter {
+                Some(voter) => match voter.push(letter_idx, *letter) {
+                    Some(winner) => winner,
+                    None => {
+                        is_confirmed = false;
+                        continue;
+                    }
+                },
+                None => *letter,
+            };
+            if letter == EARLY_RETURN {
                 self.early_idx = Some(letter_idx);
             } else if Some(letter_idx) == self.early_idx {
                 self.early_idx = None;
@@ -133,11 +139,11 @@ impl RtDecoder {
                 letter_idx < MAX_RT_LENGTH,
                 "Index should always be within bounds"
             );
-            self.buffer[letter_idx] = rt_char;
+            self.buffer.set(letter_idx, letter);
+        }
+        if is_confirmed {
+            self.buffer.set_received(segment_idx);
         }
-        self.received_segments
-            .set_bit(segment_idx)
-            .expect("Segment index should always be less than NUM_SEGMENTS");
     }
 
     fn is_reset_needed(&self, _group: Group, text_ab: bool) -> bool {
@@ -153,17 +159,20 @@ impl RtDecoder {
     }
 
     fn internal_reset(&mut self, current_group: Option<Group>, text_ab: Option<bool>) {
-        self.buffer = EMPTY_RT;
+        self.buffer.reset();
         self.current_group = current_group;
         self.text_ab = text_ab;
-        self.received_segments.reset();
         self.early_idx = None;
+        if let Some(voter) = &mut self.voter {
+            voter.reset();
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::RdsCodeTable;
 
     #[test]
     fn test_new_decoder_is_empty() {
@@ -171,7 +180,7 @@ mod tests {
         assert_eq!(decoder.current_group, None);
         assert_eq!(decoder.text_ab, None);
         assert_eq!(decoder.early_idx, None);
-        assert_eq!(decoder.buffer, EMPTY_RT);
+        assert_eq!(decoder.buffer.bytes(), &[b' '; MAX_RT_LENGTH]);
         assert_eq!(decoder.confirmed(), None);
     }
 
@@ -183,7 +192,7 @@ mod tests {
         for i in 0..NUM_SEGMENTS {
             decoder.push_segment_a(i, chars, text_ab);
         }
-        let expected_text = String::from("TEST".repeat(NUM_SEGMENTS));
+        let expected_text = "TEST".repeat(NUM_SEGMENTS);
         let expected = RadioText::new(
             RadioTextString::from_iter(expected_text.chars()),
             RadioTextPlusList::new(),
@@ -199,7 +208,7 @@ mod tests {
         for i in 0..NUM_SEGMENTS {
             decoder.push_segment_b(i, chars, text_ab);
         }
-        let expected_text = String::from("OK".repeat(NUM_SEGMENTS));
+        let expected_text = "OK".repeat(NUM_SEGMENTS);
         let expected = RadioText::new(
             RadioTextString::from_iter(expected_text.chars()),
             RadioTextPlusList::new(),
@@ -236,12 +245,8 @@ mod tests {
         let text_ab = false;
         let invalid = [0xFF, 0x02, b'A', b'B'];
         decoder.push_segment_a(0, invalid, text_ab);
-        let mut expected = [' '; MAX_RT_LENGTH];
-        expected[0] = ' ';
-        expected[1] = ' ';
-        expected[2] = 'A';
-        expected[3] = 'B';
-        assert_eq!(&decoder.buffer[..4], &expected[..4]);
+        decoder.push_segment_a(1, [b'\r', b' ', b' ', b' '], text_ab);
+        assert_eq!(decoder.confirmed().unwrap().as_str(), "  AB");
     }
 
     #[test]
@@ -252,8 +257,8 @@ mod tests {
         decoder.push_segment_a(1, [b'E', b'F', b'G', b'H'], false);
         assert_eq!(decoder.text_ab, Some(false));
         // After reset, only segment 1 should be set
-        assert_eq!(&decoder.buffer[..4], &[' '; 4]);
-        assert_eq!(&decoder.buffer[4..8], &['E', 'F', 'G', 'H']);
+        assert_eq!(&decoder.buffer.bytes()[..4], b"    ");
+        assert_eq!(&decoder.buffer.bytes()[4..8], b"EFGH");
     }
 
     #[test]
@@ -272,7 +277,7 @@ mod tests {
         assert_eq!(decoder.current_group, None);
         assert_eq!(decoder.text_ab, None);
         assert_eq!(decoder.early_idx, None);
-        assert_eq!(decoder.buffer, EMPTY_RT);
+        assert_eq!(decoder.buffer.bytes(), &[b' '; MAX_RT_LENGTH]);
         assert_eq!(decoder.confirmed(), None);
     }
 
@@ -292,8 +297,7 @@ mod tests {
         // Override EARLY_RETURN with valid character
         decoder.push_segment_a(0, [b'A', b'B', b'C', b'D'], text_ab);
         assert_eq!(decoder.early_idx, None);
-        let expected = ['A', 'B', 'C', 'D'];
-        assert_eq!(&decoder.buffer[..4], &expected);
+        assert_eq!(&decoder.buffer.bytes()[..4], b"ABCD");
     }
 
     #[test]
@@ -314,4 +318,38 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_code_table_switching() {
+        let mut decoder = RtDecoder::new();
+        // ESC n selects the G2 code table, SI SI returns to G0
+        decoder.push_segment_b(0, [0x1B, 0x6E], false);
+        decoder.push_segment_b(1, [b'F', 0xC0], false);
+        decoder.push_segment_b(2, [b'M', 0x0F], false);
+        decoder.push_segment_b(3, [0x0F, 0xC0], false);
+        decoder.push_segment_b(4, [b'\r', b' '], false);
+        let rt = decoder.confirmed().unwrap();
+        assert_eq!(rt.as_str(), "F MÁ");
+        assert_eq!(rt.code_table(), RdsCodeTable::G0);
+    }
+
+    #[test]
+    fn test_voting() {
+        let mut decoder = RtDecoder::new();
+        decoder.set_voting(Some(2));
+        decoder.push_segment_b(0, [b'O', b'K'], false);
+        decoder.push_segment_b(1, [b'\r', b' '], false);
+        assert_eq!(decoder.confirmed(), None);
+        decoder.push_segment_b(0, [b'O', b'K'], false);
+        decoder.push_segment_b(1, [b'\r', b' '], false);
+        assert_eq!(decoder.confirmed().unwrap().as_str(), "OK");
+
+        // A corrupted character does not replace the confirmed one
+        decoder.push_segment_b(0, [b'O', b'X'], false);
+        assert_eq!(decoder.confirmed().unwrap().as_str(), "OK");
+
+        // A new message is voted from scratch
+        decoder.push_segment_b(0, [b'N', b'O'], true);
+        assert_eq!(decoder.confirmed(), None);
+    }
 }
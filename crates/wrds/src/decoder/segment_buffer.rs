@@ -0,0 +1,91 @@
+use crate::decoder::bitset::Bitset;
+
+/// [`SegmentBuffer`] reassembles `N` bytes transmitted in up to `SEGMENTS` addressed
+/// segments, as used by the PS, RT, PTYN and radio paging.
+///
+/// Bytes are kept raw until the message is complete, since the code table switching
+/// sequences of texts apply to the following characters.
+#[derive(Debug)]
+pub struct SegmentBuffer<const N: usize, const SEGMENTS: usize> {
+    bytes: [u8; N],
+    received: Bitset<SEGMENTS>,
+    /// Byte filling the positions that have not been received
+    empty: u8,
+}
+
+impl<const N: usize, const SEGMENTS: usize> SegmentBuffer<N, SEGMENTS> {
+    /// Create a buffer filled with `empty`
+    pub fn new(empty: u8) -> Self {
+        Self {
+            bytes: [empty; N],
+            received: Bitset::default(),
+            empty,
+        }
+    }
+
+    /// Write the segment at `index`, whose position follows from its size, and
+    /// mark it as received
+    pub fn push(&mut self, index: usize, data: &[u8]) {
+        let start = index * data.len();
+        self.bytes[start..start + data.len()].copy_from_slice(data);
+        self.set_received(index);
+    }
+
+    /// Write a single byte without marking its segment as received
+    pub fn set(&mut self, position: usize, byte: u8) {
+        self.bytes[position] = byte;
+    }
+
+    /// Mark the segment at `index` as received
+    pub fn set_received(&mut self, index: usize) {
+        self.received
+            .set_bit(index)
+            .expect("Segment index should always be less than SEGMENTS");
+    }
+
+    /// Returns the bytes received so far
+    pub fn bytes(&self) -> &[u8; N] {
+        &self.bytes
+    }
+
+    /// Returns true if all `SEGMENTS` segments are received
+    pub fn is_complete(&self) -> bool {
+        self.received.all()
+    }
+
+    /// Returns true if the first `count` segments are received
+    pub fn first_received(&self, count: usize) -> bool {
+        self.received.first_set(count)
+    }
+
+    pub fn reset(&mut self) {
+        self.bytes = [self.empty; N];
+        self.received.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reassembly() {
+        let mut buffer = SegmentBuffer::<8, 4>::new(b' ');
+        buffer.push(1, b"CD");
+        buffer.push(0, b"AB");
+        assert!(buffer.first_received(2));
+        assert!(!buffer.is_complete());
+        assert_eq!(buffer.bytes(), b"ABCD    ");
+
+        buffer.push(3, b"GH");
+        buffer.set(4, b'E');
+        assert!(!buffer.first_received(3));
+        buffer.set_received(2);
+        assert!(buffer.is_complete());
+        assert_eq!(buffer.bytes(), b"ABCDE GH");
+
+        buffer.reset();
+        assert!(!buffer.first_received(1));
+        assert_eq!(buffer.bytes(), b"        ");
+    }
+}
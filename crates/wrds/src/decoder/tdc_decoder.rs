@@ -0,0 +1,145 @@
+use crate::types::{Block2, Block3, Block4};
+
+/// Number of Transparent Data Channels (TDC)
+pub const TDC_CHANNELS: usize = 32;
+
+/// Maximum number of bytes buffered for each TDC channel
+pub const MAX_TDC_BUFFER_SIZE: usize = 64;
+
+/// Default number of bytes buffered for each TDC channel
+pub const DEFAULT_TDC_BUFFER_SIZE: usize = 32;
+
+/// Channel number in Block 2 of group 5
+const CHANNEL_BITMASK: u16 = 0x1F;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TdcDecoderError {
+    BufferSizeExceeded(usize),
+}
+
+/// Demultiplexer for the Transparent Data Channels (TDC) of group 5.
+///
+/// Each channel buffers its bytes until they are read. The oldest bytes of a
+/// channel are dropped once its buffer is full.
+#[derive(Debug)]
+pub struct TdcDecoder {
+    channels: [heapless::Deque<u8, MAX_TDC_BUFFER_SIZE>; TDC_CHANNELS],
+    buffer_size: usize,
+}
+
+impl TdcDecoder {
+    pub fn new() -> Self {
+        Self {
+            channels: core::array::from_fn(|_| heapless::Deque::new()),
+            buffer_size: DEFAULT_TDC_BUFFER_SIZE,
+        }
+    }
+
+    /// Set the number of bytes buffered for each channel.
+    ///
+    /// # Errors
+    /// Returns an error if the size exceeds [`MAX_TDC_BUFFER_SIZE`].
+    pub fn set_buffer_size(&mut self, size: usize) -> Result<(), TdcDecoderError> {
+        if size > MAX_TDC_BUFFER_SIZE {
+            return Err(TdcDecoderError::BufferSizeExceeded(size));
+        }
+        self.buffer_size = size;
+        for channel in &mut self.channels {
+            while channel.len() > size {
+                channel.pop_front();
+            }
+        }
+        Ok(())
+    }
+
+    /// Push a group 5A or 5B.
+    ///
+    /// Group 5A carries 4 bytes in Blocks 3 and 4, group 5B 2 bytes in Block 4,
+    /// so `block3` must only be provided for group 5A.
+    pub fn push_group(&mut self, block2: &Block2, block3: Option<&Block3>, block4: &Block4) {
+        let channel = usize::from(block2.0 & CHANNEL_BITMASK);
+        if let Some(block3) = block3 {
+            self.push_bytes(channel, block3.0.to_be_bytes());
+        }
+        self.push_bytes(channel, block4.0.to_be_bytes());
+    }
+
+    /// Read the buffered bytes of a channel into `buffer`.
+    ///
+    /// Returns the number of bytes read, which are removed from the channel.
+    pub fn read(&mut self, channel: usize, buffer: &mut [u8]) -> usize {
+        let Some(channel) = self.channels.get_mut(channel) else {
+            return 0;
+        };
+        let mut count = 0;
+        for byte in buffer.iter_mut() {
+            match channel.pop_front() {
+                Some(value) => *byte = value,
+                None => break,
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the number of bytes buffered for a channel
+    pub fn available(&self, channel: usize) -> usize {
+        self.channels
+            .get(channel)
+            .map_or(0, |channel| channel.len())
+    }
+
+    pub fn reset(&mut self) {
+        self.channels.iter_mut().for_each(|channel| channel.clear());
+    }
+
+    fn push_bytes(&mut self, channel: usize, bytes: [u8; 2]) {
+        let buffer = &mut self.channels[channel];
+        for byte in bytes {
+            if self.buffer_size == 0 {
+                return;
+            }
+            if buffer.len() >= self.buffer_size {
+                buffer.pop_front();
+            }
+            let _ = buffer.push_back(byte);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demultiplex() {
+        let mut decoder = TdcDecoder::new();
+        decoder.push_group(&Block2(0x5003), Some(&Block3(0x4142)), &Block4(0x4344));
+        decoder.push_group(&Block2(0x5804), None, &Block4(0x4546));
+        decoder.push_group(&Block2(0x5003), Some(&Block3(0x4748)), &Block4(0x494A));
+
+        let mut buffer = [0; 16];
+        assert_eq!(decoder.read(3, &mut buffer), 8);
+        assert_eq!(&buffer[..8], b"ABCDGHIJ");
+        assert_eq!(decoder.read(4, &mut buffer), 2);
+        assert_eq!(&buffer[..2], b"EF");
+        assert_eq!(decoder.read(3, &mut buffer), 0);
+        assert_eq!(decoder.read(TDC_CHANNELS, &mut buffer), 0);
+    }
+
+    #[test]
+    fn test_buffer_size() {
+        let mut decoder = TdcDecoder::new();
+        assert_eq!(
+            decoder.set_buffer_size(MAX_TDC_BUFFER_SIZE + 1),
+            Err(TdcDecoderError::BufferSizeExceeded(MAX_TDC_BUFFER_SIZE + 1))
+        );
+        decoder.set_buffer_size(6).unwrap();
+        decoder.push_group(&Block2(0x5000), Some(&Block3(0x4142)), &Block4(0x4344));
+        decoder.push_group(&Block2(0x5000), Some(&Block3(0x4546)), &Block4(0x4748));
+
+        let mut buffer = [0; 16];
+        assert_eq!(decoder.read(0, &mut buffer), 6);
+        assert_eq!(&buffer[..6], b"CDEFGH");
+    }
+}
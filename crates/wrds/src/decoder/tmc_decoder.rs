@@ -0,0 +1,278 @@
+use crate::types::{
+    Block2, Block3, Block4, TmcDirection, TmcKeyTable, TmcKeyTableSelector, TmcLabelList,
+    TmcMessage, TmcService,
+};
+
+use self::{multi_group::MultiGroupAssembler, service::ServiceDecoder};
+
+pub(crate) mod events;
+mod multi_group;
+mod service;
+
+/// Tuning information flag (T) in Block 2
+const TUNING_BITMASK: u16 = 0x10;
+
+/// Single-group message flag (F) in Block 2
+const SINGLE_GROUP_BITMASK: u16 = 0x08;
+
+/// Duration and persistence (DP) in Block 2
+const DURATION_BITMASK: u16 = 0x07;
+
+/// Continuity index (CI) of multi-group messages in Block 2
+const CONTINUITY_INDEX_BITMASK: u16 = 0x07;
+
+/// First group indicator (FG) of multi-group messages in Block 3
+const FIRST_GROUP_BITMASK: u16 = 0x8000;
+
+/// Diversion advice (D) in Block 3
+const DIVERSION_BITMASK: u16 = 0x8000;
+
+/// Direction (+/-) in Block 3
+const DIRECTION_BITMASK: u16 = 0x4000;
+
+/// Extent in Block 3
+const EXTENT_SHIFT: usize = 11;
+const EXTENT_BITMASK: u16 = 0x7;
+
+/// Event code in Block 3
+const EVENT_BITMASK: u16 = 0x7FF;
+
+/// Decoder for Traffic Message Channel (TMC) groups, following ALERT-C.
+#[derive(Debug)]
+pub struct TmcDecoder {
+    /// Raw content of the last user message group, used to drop immediate repetitions
+    last_group: Option<(u16, u16, u16)>,
+    /// Last decoded message, used to drop repetitions of multi-group messages
+    last_message: Option<TmcMessage>,
+    multi_group: MultiGroupAssembler,
+    service: ServiceDecoder,
+    /// Key tables supplied by the user, kept across resets
+    service_keys: Option<TmcKeyTable>,
+    test_keys: Option<TmcKeyTable>,
+}
+
+impl TmcDecoder {
+    /// Creates new TmcDecoder
+    pub fn new() -> Self {
+        Self {
+            last_group: None,
+            last_message: None,
+            multi_group: MultiGroupAssembler::new(),
+            service: ServiceDecoder::new(),
+            service_keys: None,
+            test_keys: None,
+        }
+    }
+
+    /// Set the key table used to decrypt the location codes of encrypted services
+    pub fn set_key_table(&mut self, selector: TmcKeyTableSelector, table: TmcKeyTable) {
+        match selector {
+            TmcKeyTableSelector::Service => self.service_keys = Some(table),
+            TmcKeyTableSelector::Test => self.test_keys = Some(table),
+        }
+    }
+
+    /// Push the TMC system information from the message bits of group 3A
+    pub fn push_system_information(&mut self, message: u16) {
+        self.service.push_system_information(message);
+    }
+
+    /// Returns the description of the TMC service, if any has been received
+    pub fn service(&self) -> Option<&TmcService> {
+        self.service.service()
+    }
+
+    /// Push a new TMC group.
+    ///
+    /// Returns the decoded message if the group is a single-group user message or
+    /// completes a multi-group user message, unless it repeats the previous message.
+    /// Tuning information and encryption administration groups update the service
+    /// description instead.
+    ///
+    /// Messages of an encrypted service are dropped unless the key table it uses is set.
+    pub fn push_group(
+        &mut self,
+        block2: &Block2,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<TmcMessage> {
+        if block2.0 & TUNING_BITMASK != 0 {
+            self.service
+                .push_tuning_information(block2.0, block3, block4);
+            return None;
+        }
+
+        let group = (block2.0 & 0x1F, block3.0, block4.0);
+        if self.last_group == Some(group) {
+            return None;
+        }
+        self.last_group = Some(group);
+
+        let message = if block2.0 & SINGLE_GROUP_BITMASK != 0 {
+            self.single_group_message(block2, block3, block4)?
+        } else {
+            self.push_multi_group(block2, block3, block4)?
+        };
+
+        if self.last_message.as_ref() == Some(&message) {
+            return None;
+        }
+        self.last_message = Some(message.clone());
+        Some(message)
+    }
+
+    pub fn reset(&mut self) {
+        self.last_group = None;
+        self.last_message = None;
+        self.multi_group.reset();
+        self.service.reset();
+    }
+
+    fn single_group_message(
+        &self,
+        block2: &Block2,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<TmcMessage> {
+        Some(TmcMessage {
+            event: block3.0 & EVENT_BITMASK,
+            location: self.decrypt(block4.0)?,
+            direction: TmcDirection::from(block3.0 & DIRECTION_BITMASK != 0),
+            extent: ((block3.0 >> EXTENT_SHIFT) & EXTENT_BITMASK) as u8,
+            duration: (block2.0 & DURATION_BITMASK) as u8,
+            diversion: block3.0 & DIVERSION_BITMASK != 0,
+            labels: TmcLabelList::new(),
+        })
+    }
+
+    /// Returns the clear location code, or `None` if the key table of an encrypted
+    /// service is not set.
+    fn decrypt(&self, location: u16) -> Option<u16> {
+        let Some(encryption) = self.service().and_then(|service| service.encryption) else {
+            return Some(location);
+        };
+        let keys = match encryption.key_table {
+            TmcKeyTableSelector::Service => self.service_keys.as_ref(),
+            TmcKeyTableSelector::Test => self.test_keys.as_ref(),
+        }?;
+        keys.get(usize::from(encryption.encryption_id))
+            .map(|key| key.decrypt(location))
+    }
+
+    fn push_multi_group(
+        &mut self,
+        block2: &Block2,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<TmcMessage> {
+        let continuity_index = (block2.0 & CONTINUITY_INDEX_BITMASK) as u8;
+        // Continuity index 0 carries the encryption administration
+        if continuity_index == 0 {
+            self.service.push_encryption_administration(block3, block4);
+            return None;
+        }
+        if block3.0 & FIRST_GROUP_BITMASK == 0 {
+            return self
+                .multi_group
+                .push_subsequent(continuity_index, block3, block4);
+        }
+        let location = self.decrypt(block4.0)?;
+        self.multi_group.push_first(
+            continuity_index,
+            block3.0 & EVENT_BITMASK,
+            location,
+            TmcDirection::from(block3.0 & DIRECTION_BITMASK != 0),
+            ((block3.0 >> EXTENT_SHIFT) & EXTENT_BITMASK) as u8,
+        );
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TmcEncryptionKey, TmcLabel, TMC_KEY_TABLE_SIZE};
+
+    /// Group 8A, single-group message, duration 2
+    const BLOCK2: Block2 = Block2(0x800A);
+    /// Diversion, negative direction, extent 3, event 101
+    const BLOCK3: Block3 = Block3(0xD865);
+    /// Location 12345
+    const BLOCK4: Block4 = Block4(0x3039);
+
+    #[test]
+    fn test_single_group_message() {
+        let mut decoder = TmcDecoder::new();
+        assert_eq!(
+            decoder.push_group(&BLOCK2, &BLOCK3, &BLOCK4),
+            Some(TmcMessage {
+                event: 101,
+                location: 12345,
+                direction: TmcDirection::Negative,
+                extent: 3,
+                duration: 2,
+                diversion: true,
+                labels: TmcLabelList::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_multi_group_message() {
+        let mut decoder = TmcDecoder::new();
+        // First group, CI 5: positive direction, extent 1, event 101, location 12345
+        let first = (Block2(0x8005), Block3(0x8865), Block4(0x3039));
+        // Second group, GSI 0: label 4 (quantifier 7)
+        let second = (Block2(0x8005), Block3(0x4000 | 0x438), Block4(0x0000));
+        for _ in 0..2 {
+            assert_eq!(decoder.push_group(&first.0, &first.1, &first.2), None);
+        }
+        let message = decoder.push_group(&second.0, &second.1, &second.2).unwrap();
+        assert_eq!(message.event, 101);
+        assert_eq!(message.location, 12345);
+        assert_eq!(message.extent, 1);
+        assert_eq!(message.labels.as_slice(), &[TmcLabel::Quantifier5(7)]);
+
+        // Repetition of the whole message is dropped
+        assert_eq!(decoder.push_group(&first.0, &first.1, &first.2), None);
+        assert_eq!(decoder.push_group(&second.0, &second.1, &second.2), None);
+    }
+
+    #[test]
+    fn test_repetition_is_dropped() {
+        let mut decoder = TmcDecoder::new();
+        assert!(decoder.push_group(&BLOCK2, &BLOCK3, &BLOCK4).is_some());
+        assert!(decoder.push_group(&BLOCK2, &BLOCK3, &BLOCK4).is_none());
+        decoder.reset();
+        assert!(decoder.push_group(&BLOCK2, &BLOCK3, &BLOCK4).is_some());
+    }
+
+    #[test]
+    fn test_encrypted_location() {
+        let key = TmcEncryptionKey::new(0x5A, 3, 7);
+        let mut keys = [TmcEncryptionKey::default(); TMC_KEY_TABLE_SIZE];
+        keys[7] = key;
+
+        let mut decoder = TmcDecoder::new();
+        // Encryption administration: service key table, SID 1, ENCID 7
+        assert!(decoder
+            .push_group(&Block2(0x8000), &Block3((1 << 5) | 7), &Block4(0x0000))
+            .is_none());
+        let block4 = Block4(key.encrypt(12345));
+        assert!(decoder.push_group(&BLOCK2, &BLOCK3, &block4).is_none());
+
+        decoder.set_key_table(TmcKeyTableSelector::Service, keys);
+        decoder.reset();
+        decoder.push_group(&Block2(0x8000), &Block3((1 << 5) | 7), &Block4(0x0000));
+        let message = decoder.push_group(&BLOCK2, &BLOCK3, &block4).unwrap();
+        assert_eq!(message.location, 12345);
+    }
+
+    #[test]
+    fn test_tuning_is_ignored() {
+        let mut decoder = TmcDecoder::new();
+        assert!(decoder
+            .push_group(&Block2(0x8010), &BLOCK3, &BLOCK4)
+            .is_none());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::types::{TmcDurationType, TmcEventInfo, TmcEventNature, TmcQuantifierType, TmcQuantity};
+
+use TmcDurationType::{Dynamic as D, LongerLasting as L};
+use TmcEventNature::{Forecast as F, Information as I};
+
+const fn event(
+    code: u16,
+    text: &'static str,
+    nature: TmcEventNature,
+    update_class: u8,
+    duration_type: TmcDurationType,
+    quantifier: Option<TmcQuantifierType>,
+) -> TmcEventInfo {
+    TmcEventInfo {
+        code,
+        text,
+        nature,
+        update_class,
+        duration_type,
+        quantifier,
+    }
+}
+
+const SPEED: Option<TmcQuantifierType> = Some(TmcQuantifierType::Speed);
+const SMALL_NUMBER: Option<TmcQuantifierType> = Some(TmcQuantifierType::SmallNumber);
+const NONE: Option<TmcQuantifierType> = None;
+
+/// Event code cancelling all messages for the location and direction.
+///
+/// This is the only cancellation code used by the crate; it is handled by the
+/// message store rather than looked up in the event list.
+pub const TMC_CANCELLATION_EVENT: u16 = 2047;
+
+/// Events from the ALERT-C event list (ISO 14819-2), sorted by code.
+///
+/// This is only a subset of the event list, covering some level of service,
+/// accident, closure and roadworks events. Most codes of the full list are
+/// missing and are not looked up.
+#[rustfmt::skip]
+const EVENTS: &[TmcEventInfo] = &[
+    event(1, "traffic problem", I, 1, D, NONE),
+    event(101, "stationary traffic", I, 1, D, NONE),
+    event(102, "stationary traffic for 1 km", I, 1, D, NONE),
+    event(103, "stationary traffic for 2 km", I, 1, D, NONE),
+    event(104, "stationary traffic for 4 km", I, 1, D, NONE),
+    event(105, "stationary traffic for 6 km", I, 1, D, NONE),
+    event(106, "stationary traffic for 10 km", I, 1, D, NONE),
+    event(107, "stationary traffic expected", F, 2, D, NONE),
+    event(108, "queuing traffic (with average speeds Q)", I, 1, D, SPEED),
+    event(109, "queuing traffic for 1 km (with average speeds Q)", I, 1, D, SPEED),
+    event(110, "queuing traffic for 2 km (with average speeds Q)", I, 1, D, SPEED),
+    event(111, "queuing traffic for 4 km (with average speeds Q)", I, 1, D, SPEED),
+    event(112, "queuing traffic for 6 km (with average speeds Q)", I, 1, D, SPEED),
+    event(113, "queuing traffic for 10 km (with average speeds Q)", I, 1, D, SPEED),
+    event(114, "queuing traffic expected", F, 2, D, NONE),
+    event(115, "slow traffic (with average speeds Q)", I, 1, D, SPEED),
+    event(116, "slow traffic for 1 km (with average speeds Q)", I, 1, D, SPEED),
+    event(117, "slow traffic for 2 km (with average speeds Q)", I, 1, D, SPEED),
+    event(118, "slow traffic for 4 km (with average speeds Q)", I, 1, D, SPEED),
+    event(119, "slow traffic for 6 km (with average speeds Q)", I, 1, D, SPEED),
+    event(120, "slow traffic for 10 km (with average speeds Q)", I, 1, D, SPEED),
+    event(121, "slow traffic expected", F, 2, D, NONE),
+    event(122, "heavy traffic (with average speeds Q)", I, 1, D, SPEED),
+    event(123, "heavy traffic expected", F, 2, D, NONE),
+    event(124, "traffic flowing freely (with average speeds Q)", I, 1, D, SPEED),
+    event(125, "traffic building up (with average speeds Q)", I, 1, D, SPEED),
+    event(126, "no problems to report", I, 1, D, NONE),
+    event(127, "traffic congestion cleared", I, 1, D, NONE),
+    event(129, "stationary traffic for 3 km", I, 1, D, NONE),
+    event(130, "danger of stationary traffic", I, 1, D, NONE),
+    event(131, "queuing traffic for 3 km (with average speeds Q)", I, 1, D, SPEED),
+    event(132, "danger of queuing traffic (with average speeds Q)", I, 1, D, SPEED),
+    event(133, "long queues (with average speeds Q)", I, 1, D, SPEED),
+    event(134, "slow traffic for 3 km (with average speeds Q)", I, 1, D, SPEED),
+    event(135, "traffic easing", I, 1, D, NONE),
+    event(136, "traffic congestion (with average speeds Q)", I, 1, D, SPEED),
+    event(137, "traffic lighter than normal (with average speeds Q)", I, 1, D, SPEED),
+    event(201, "accident(s)", I, 3, D, NONE),
+    event(202, "serious accident(s)", I, 3, D, NONE),
+    event(203, "multi-vehicle accident (involving Q vehicles)", I, 3, D, SMALL_NUMBER),
+    event(204, "accident involving heavy lorries", I, 3, D, NONE),
+    event(401, "closed", I, 5, L, NONE),
+    event(701, "roadworks", I, 11, L, NONE),
+    event(702, "major roadworks", I, 11, L, NONE),
+    event(703, "maintenance work", I, 11, L, NONE),
+];
+
+/// Look up an ALERT-C event code in the built-in subset of the event list.
+///
+/// Returns `None` if the code is not in the subset, which is the case for most
+/// codes of the full event list.
+pub fn lookup_event(code: u16) -> Option<&'static TmcEventInfo> {
+    EVENTS
+        .binary_search_by_key(&code, |info| info.code)
+        .ok()
+        .map(|index| &EVENTS[index])
+}
+
+/// Interpret a quantifier value according to its type.
+///
+/// 5-bit quantifiers (label 4) are used for types up to [`TmcQuantifierType::Length`],
+/// 8-bit quantifiers (label 5) for the remaining types, see
+/// [`TmcQuantifierType::is_five_bit`].
+pub fn interpret_quantifier(quantifier: TmcQuantifierType, value: u8) -> TmcQuantity {
+    // 5-bit quantifiers use 0 to represent the value after 31
+    let n = u16::from(if value == 0 { 32 } else { value });
+    match quantifier {
+        TmcQuantifierType::SmallNumber => {
+            TmcQuantity::Count(if n <= 28 { n } else { n + (n - 28) })
+        }
+        TmcQuantifierType::Number => TmcQuantity::Count(match n {
+            0..=4 => n,
+            5..=14 => (n - 4) * 10,
+            _ => (n - 12) * 50,
+        }),
+        TmcQuantifierType::LessThanMetres => TmcQuantity::Metres(n * 10),
+        TmcQuantifierType::Percentage => TmcQuantity::Percent((n - 1) * 5),
+        TmcQuantifierType::Speed => TmcQuantity::SpeedKmh(n * 5),
+        TmcQuantifierType::Length => TmcQuantity::LengthMetres(match n {
+            0..=10 => n * 100,
+            11..=19 => (n - 9) * 1000,
+            _ => (n - 14) * 2000,
+        }),
+        TmcQuantifierType::Temperature => TmcQuantity::Celsius(i16::from(value) - 51),
+        TmcQuantifierType::Time => {
+            let minutes = u16::from(value.saturating_sub(1)) * 10;
+            TmcQuantity::Time {
+                hours: (minutes / 60) as u8,
+                minutes: (minutes % 60) as u8,
+            }
+        }
+        TmcQuantifierType::Weight => TmcQuantity::TenthsOfTonnes(tenths(value)),
+        TmcQuantifierType::Dimension => TmcQuantity::TenthsOfMetres(tenths(value)),
+        TmcQuantifierType::Precipitation => TmcQuantity::PrecipitationMm(value),
+        TmcQuantifierType::FrequencyMhz => {
+            TmcQuantity::FrequencyKhz(87_500 + u32::from(value) * 100)
+        }
+        TmcQuantifierType::FrequencyKhz => TmcQuantity::FrequencyKhz(144 + u32::from(value) * 9),
+    }
+}
+
+/// Weights and dimensions step by 0.1 up to 10 and by 0.5 above
+fn tenths(value: u8) -> u16 {
+    let value = u16::from(value);
+    if value <= 100 {
+        value
+    } else {
+        100 + (value - 100) * 5
+    }
+}
+
+/// Length of route affected (label 2) in km, `None` meaning more than 100 km
+pub fn route_length_km(value: u8) -> Option<u8> {
+    match value {
+        0 => None,
+        1..=10 => Some(value),
+        11..=15 => Some(10 + (value - 10) * 2),
+        _ => Some(25 + (value - 16) * 5),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_sorted() {
+        assert!(EVENTS.windows(2).all(|pair| pair[0].code < pair[1].code));
+    }
+
+    #[test]
+    fn test_lookup_event() {
+        let info = lookup_event(101).unwrap();
+        assert_eq!(info.text, "stationary traffic");
+        assert_eq!(info.update_class, 1);
+        assert_eq!(info.duration_type, TmcDurationType::Dynamic);
+        assert_eq!(lookup_event(701).unwrap().update_class, 11);
+        assert_eq!(lookup_event(2000), None);
+    }
+
+    #[test]
+    fn test_interpret_quantifier() {
+        assert_eq!(
+            interpret_quantifier(TmcQuantifierType::Speed, 6),
+            TmcQuantity::SpeedKmh(30)
+        );
+        assert_eq!(
+            interpret_quantifier(TmcQuantifierType::Speed, 0),
+            TmcQuantity::SpeedKmh(160)
+        );
+        assert_eq!(
+            interpret_quantifier(TmcQuantifierType::SmallNumber, 29),
+            TmcQuantity::Count(30)
+        );
+        assert_eq!(
+            interpret_quantifier(TmcQuantifierType::Temperature, 41),
+            TmcQuantity::Celsius(-10)
+        );
+        assert_eq!(
+            interpret_quantifier(TmcQuantifierType::Time, 52),
+            TmcQuantity::Time {
+                hours: 8,
+                minutes: 30
+            }
+        );
+    }
+
+    #[test]
+    fn test_quantity_label_width() {
+        use crate::types::{TmcDirection, TmcLabel, TmcLabelList, TmcMessage};
+
+        // Queuing traffic with a speed quantifier, which is a 5-bit type
+        let message = |label| TmcMessage {
+            event: 108,
+            location: 1000,
+            direction: TmcDirection::Positive,
+            extent: 1,
+            duration: 0,
+            diversion: false,
+            labels: TmcLabelList::from_slice(&[label]).unwrap(),
+        };
+        assert_eq!(
+            message(TmcLabel::Quantifier5(6)).quantity(),
+            Some(TmcQuantity::SpeedKmh(30))
+        );
+        assert_eq!(message(TmcLabel::Quantifier8(6)).quantity(), None);
+        assert!(!TmcQuantifierType::Temperature.is_five_bit());
+    }
+
+    #[test]
+    fn test_route_length() {
+        assert_eq!(route_length_km(0), None);
+        assert_eq!(route_length_km(4), Some(4));
+        assert_eq!(route_length_km(15), Some(20));
+        assert_eq!(route_length_km(31), Some(100));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::types::{Block3, Block4, TmcDirection, TmcLabel, TmcLabelList, TmcMessage};
+
+/// Second group indicator (SG) in Block 3 of subsequent groups
+const SECOND_GROUP_BITMASK: u16 = 0x4000;
+
+/// Group sequence identifier (GSI) in Block 3 of subsequent groups
+const GSI_SHIFT: usize = 12;
+const GSI_BITMASK: u16 = 0x3;
+
+/// Free-format bits in Block 3 of subsequent groups
+const FREE_FORMAT_BITMASK: u16 = 0xFFF;
+
+/// Number of free-format bits carried by each subsequent group
+const FREE_FORMAT_BITS_PER_GROUP: u32 = 28;
+
+/// Number of bits of a label
+const LABEL_BITS: u32 = 4;
+
+/// Number of value bits for each of the 16 labels
+const LABEL_VALUE_BITS: [u32; 16] = [3, 3, 5, 5, 5, 8, 8, 8, 8, 11, 16, 16, 16, 16, 0, 0];
+
+/// Control code (label 1) setting the diversion advice
+const CONTROL_DIVERSION: u8 = 5;
+/// Control code (label 1) increasing the extent by 8
+const CONTROL_EXTENT_8: u8 = 6;
+/// Control code (label 1) increasing the extent by 16
+const CONTROL_EXTENT_16: u8 = 7;
+
+/// Largest extent of an ALERT-C message: 3 bits increased by 8 and 16
+const MAX_EXTENT: u8 = 31;
+
+/// First group of a multi-group message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FirstGroup {
+    event: u16,
+    location: u16,
+    direction: TmcDirection,
+    extent: u8,
+}
+
+/// Multi-group message that is being reassembled
+#[derive(Debug)]
+struct Partial {
+    continuity_index: u8,
+    first: FirstGroup,
+    /// Expected group sequence identifier of the next group, `None` if the second group is expected
+    next_gsi: Option<u8>,
+    free_format: u128,
+    free_format_len: u32,
+}
+
+/// Reassembles ALERT-C multi-group messages by continuity index and group sequence.
+#[derive(Debug)]
+pub struct MultiGroupAssembler {
+    partial: Option<Partial>,
+}
+
+impl MultiGroupAssembler {
+    pub fn new() -> Self {
+        Self { partial: None }
+    }
+
+    /// Push the first group of a message, discarding any incomplete message.
+    pub fn push_first(
+        &mut self,
+        continuity_index: u8,
+        event: u16,
+        location: u16,
+        direction: TmcDirection,
+        extent: u8,
+    ) {
+        self.partial = Some(Partial {
+            continuity_index,
+            first: FirstGroup {
+                event,
+                location,
+                direction,
+                extent,
+            },
+            next_gsi: None,
+            free_format: 0,
+            free_format_len: 0,
+        });
+    }
+
+    /// Push a subsequent group of a message.
+    ///
+    /// Returns the complete message once its last group has been received.
+    /// Groups out of sequence discard the message being reassembled.
+    pub fn push_subsequent(
+        &mut self,
+        continuity_index: u8,
+        block3: &Block3,
+        block4: &Block4,
+    ) -> Option<TmcMessage> {
+        let mut partial = self.partial.take()?;
+        if partial.continuity_index != continuity_index {
+            return None;
+        }
+
+        let is_second = block3.0 & SECOND_GROUP_BITMASK != 0;
+        let gsi = ((block3.0 >> GSI_SHIFT) & GSI_BITMASK) as u8;
+        match partial.next_gsi {
+            None if is_second => {}
+            Some(expected) if !is_second && gsi == expected => {}
+            _ => return None,
+        }
+
+        let bits = (u32::from(block3.0 & FREE_FORMAT_BITMASK) << 16) | u32::from(block4.0);
+        partial.free_format =
+            (partial.free_format << FREE_FORMAT_BITS_PER_GROUP) | u128::from(bits);
+        partial.free_format_len += FREE_FORMAT_BITS_PER_GROUP;
+
+        if gsi == 0 {
+            return Some(Self::complete(&partial));
+        }
+        partial.next_gsi = Some(gsi - 1);
+        self.partial = Some(partial);
+        None
+    }
+
+    pub fn reset(&mut self) {
+        self.partial = None;
+    }
+
+    fn complete(partial: &Partial) -> TmcMessage {
+        let labels = parse_labels(partial.free_format, partial.free_format_len);
+        let mut message = TmcMessage {
+            event: partial.first.event,
+            location: partial.first.location,
+            direction: partial.first.direction,
+            extent: partial.first.extent,
+            duration: 0,
+            diversion: false,
+            labels: TmcLabelList::new(),
+        };
+        for label in &labels {
+            match *label {
+                TmcLabel::Duration(duration) => message.duration = duration,
+                TmcLabel::ControlCode(CONTROL_DIVERSION) => message.diversion = true,
+                TmcLabel::ControlCode(CONTROL_EXTENT_8) => {
+                    message.extent = message.extent.saturating_add(8)
+                }
+                TmcLabel::ControlCode(CONTROL_EXTENT_16) => {
+                    message.extent = message.extent.saturating_add(16)
+                }
+                _ => {}
+            }
+        }
+        message.extent = message.extent.min(MAX_EXTENT);
+        message.labels = labels;
+        message
+    }
+}
+
+/// Parse the free-format bitstream of a multi-group message into labels.
+///
+/// `bits` holds `len` bits aligned to the least significant bit. Parsing stops
+/// at trailing zero padding or when a label value is truncated.
+pub fn parse_labels(bits: u128, len: u32) -> TmcLabelList {
+    let mut labels = TmcLabelList::new();
+    let mut remaining = len;
+
+    let read = |remaining: &mut u32, size: u32| -> u16 {
+        *remaining -= size;
+        ((bits >> *remaining) & ((1 << size) - 1)) as u16
+    };
+
+    while remaining >= LABEL_BITS {
+        let rest_mask = (1u128 << remaining) - 1;
+        if bits & rest_mask == 0 {
+            break;
+        }
+        let label = read(&mut remaining, LABEL_BITS) as u8;
+        let size = LABEL_VALUE_BITS[usize::from(label)];
+        if size > remaining {
+            break;
+        }
+        let value = read(&mut remaining, size);
+        let parsed = match label {
+            0 => TmcLabel::Duration(value as u8),
+            1 => TmcLabel::ControlCode(value as u8),
+            2 => TmcLabel::RouteLength(value as u8),
+            3 => TmcLabel::SpeedLimit(value as u8),
+            4 => TmcLabel::Quantifier5(value as u8),
+            5 => TmcLabel::Quantifier8(value as u8),
+            6 => TmcLabel::SupplementaryInformation(value as u8),
+            7 => TmcLabel::StartTime(value as u8),
+            8 => TmcLabel::StopTime(value as u8),
+            9 => TmcLabel::AdditionalEvent(value),
+            10 => TmcLabel::DiversionRoute(value),
+            11 => TmcLabel::Destination(value),
+            13 => TmcLabel::CrossLinkage(value),
+            14 => TmcLabel::Separator,
+            _ => TmcLabel::Reserved { label, value },
+        };
+        if labels.push(parsed).is_err() {
+            break;
+        }
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pack `(value, size)` fields into a bitstream, most significant field first
+    fn pack(fields: &[(u128, u32)]) -> (u128, u32) {
+        fields.iter().fold((0, 0), |(bits, len), &(value, size)| {
+            ((bits << size) | value, len + size)
+        })
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        // Label 0 (duration 3), label 5 (quantifier 0xAB), label 9 (event 0x123), padding
+        let (bits, len) = pack(&[
+            (0, 4),
+            (3, 3),
+            (5, 4),
+            (0xAB, 8),
+            (9, 4),
+            (0x123, 11),
+            (0, 22),
+        ]);
+        assert_eq!(len, 2 * FREE_FORMAT_BITS_PER_GROUP);
+        assert_eq!(
+            parse_labels(bits, len).as_slice(),
+            &[
+                TmcLabel::Duration(3),
+                TmcLabel::Quantifier8(0xAB),
+                TmcLabel::AdditionalEvent(0x123),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_labels_stops_at_truncated_value() {
+        // Label 10 requires 16 bits but only 8 are left
+        let bits: u128 = (0xA << 8) | 0xFF;
+        assert_eq!(parse_labels(bits, 12).as_slice(), &[]);
+    }
+
+    #[test]
+    fn test_reassembly() {
+        let mut assembler = MultiGroupAssembler::new();
+        assembler.push_first(3, 101, 12345, TmcDirection::Positive, 1);
+        // Second group, GSI 1: label 0 (duration 2), label 1 (control code 6)
+        let (bits, _) = pack(&[(0, 4), (2, 3), (1, 4), (6, 3), (0, 14)]);
+        let block3 = Block3(0x5000 | (bits >> 16) as u16);
+        let block4 = Block4(bits as u16);
+        assert_eq!(assembler.push_subsequent(3, &block3, &block4), None);
+        // Third group, GSI 0: only padding
+        let message = assembler
+            .push_subsequent(3, &Block3(0x0000), &Block4(0x0000))
+            .unwrap();
+        assert_eq!(message.event, 101);
+        assert_eq!(message.location, 12345);
+        assert_eq!(message.duration, 2);
+        assert_eq!(message.extent, 9);
+        assert_eq!(
+            message.labels.as_slice(),
+            &[TmcLabel::Duration(2), TmcLabel::ControlCode(6)]
+        );
+    }
+
+    #[test]
+    fn test_out_of_sequence_discards_message() {
+        let mut assembler = MultiGroupAssembler::new();
+        assembler.push_first(3, 101, 12345, TmcDirection::Positive, 1);
+        // Subsequent group without the second group indicator
+        assert_eq!(
+            assembler.push_subsequent(3, &Block3(0x0000), &Block4(0x0000)),
+            None
+        );
+        // Continuity index does not match
+        assembler.push_first(3, 101, 12345, TmcDirection::Positive, 1);
+        assert_eq!(
+            assembler.push_subsequent(4, &Block3(0x4000), &Block4(0x0000)),
+            None
+        );
+        assert_eq!(
+            assembler.push_subsequent(3, &Block3(0x4000), &Block4(0x0000)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extent_is_clamped() {
+        // 16 labels increasing the extent by 16 fill the 112 free-format bits
+        let fields = [(1, 4), (u128::from(CONTROL_EXTENT_16), 3)];
+        let (free_format, free_format_len) = pack(&fields.repeat(16));
+        assert_eq!(free_format_len, 4 * FREE_FORMAT_BITS_PER_GROUP);
+        let partial = Partial {
+            continuity_index: 1,
+            first: FirstGroup {
+                event: 101,
+                location: 12345,
+                direction: TmcDirection::Positive,
+                extent: 7,
+            },
+            next_gsi: Some(0),
+            free_format,
+            free_format_len,
+        };
+        let message = MultiGroupAssembler::complete(&partial);
+        assert_eq!(message.labels.len(), 16);
+        assert_eq!(message.extent, MAX_EXTENT);
+    }
+}
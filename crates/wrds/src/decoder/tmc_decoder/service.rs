@@ -0,0 +1,298 @@
+use crate::{
+    decoder::{bitset::Bitset, rds_charset::to_basic_rds_char},
+    types::{
+        Block3, Block4, TmcEncryption, TmcKeyTableSelector, TmcOtherNetwork, TmcProviderNameString,
+        TmcScope, TmcService, TMC_PROVIDER_NAME_LENGTH,
+    },
+};
+
+/// Variant code of the system information in group 3A
+const SYSTEM_VARIANT_SHIFT: usize = 14;
+
+/// Location table number (LTN) in group 3A variant 0 and tuning variant 9
+const LTN_BITMASK: u16 = 0x3F;
+
+/// Gap parameter in group 3A variant 1
+const GAP_SHIFT: usize = 12;
+const GAP_BITMASK: u16 = 0x3;
+
+/// Number of groups between TMC groups for each gap parameter value
+const GAP_GROUPS: [u8; 4] = [3, 5, 8, 11];
+
+/// Service identifier (SID) in group 3A variant 1, tuning variant 9 and the encryption administration
+const SID_BITMASK: u16 = 0x3F;
+
+/// Variant code of the encryption administration in Block 3
+const ENCRYPTION_VARIANT_SHIFT: usize = 13;
+
+/// Test mode bits of the encryption administration in Block 3
+const ENCRYPTION_TEST_SHIFT: usize = 11;
+const ENCRYPTION_TEST_BITMASK: u16 = 0x3;
+
+/// Encryption ID (ENCID) of the encryption administration in Block 3
+const ENCID_BITMASK: u16 = 0x1F;
+
+/// Location table number before encryption (LTNBE) in Block 4
+const LTNBE_SHIFT: usize = 10;
+
+/// Variant code of tuning information in Block 2
+const TUNING_VARIANT_BITMASK: u16 = 0x0F;
+
+/// Number of provider name characters in each tuning group
+const PROVIDER_SEGMENT_SIZE: usize = 4;
+
+/// Lowest FM frequency (87.5 MHz) in kHz
+const FM_BASE_KHZ: u32 = 87_500;
+/// FM channel spacing in kHz
+const FM_SPACING_KHZ: u32 = 100;
+/// Highest valid FM frequency code (108.0 MHz)
+const FM_CODE_MAX: u8 = 204;
+
+/// Decoder for the TMC service description
+#[derive(Debug)]
+pub struct ServiceDecoder {
+    service: Option<TmcService>,
+    provider_name: [char; TMC_PROVIDER_NAME_LENGTH],
+    provider_segments: Bitset<2>,
+}
+
+impl ServiceDecoder {
+    pub fn new() -> Self {
+        Self {
+            service: None,
+            provider_name: [' '; TMC_PROVIDER_NAME_LENGTH],
+            provider_segments: Bitset::default(),
+        }
+    }
+
+    /// Push the TMC system information from the message bits of group 3A
+    pub fn push_system_information(&mut self, message: u16) {
+        let service = self.service.get_or_insert_with(TmcService::default);
+        match message >> SYSTEM_VARIANT_SHIFT {
+            0 => {
+                service.location_table = ((message >> 6) & LTN_BITMASK) as u8;
+                service.alternative_frequencies = message & 0x20 != 0;
+                service.enhanced_mode = message & 0x10 != 0;
+                service.scope = scope(message);
+            }
+            1 => {
+                let gap = usize::from((message >> GAP_SHIFT) & GAP_BITMASK);
+                service.gap = Some(GAP_GROUPS[gap]);
+                service.service_id = Some(((message >> 6) & SID_BITMASK) as u8);
+            }
+            _ => {}
+        }
+    }
+
+    /// Push a TMC tuning information group
+    pub fn push_tuning_information(&mut self, variant: u16, block3: &Block3, block4: &Block4) {
+        let service = self.service.get_or_insert_with(TmcService::default);
+        match variant & TUNING_VARIANT_BITMASK {
+            variant @ (4 | 5) => {
+                let index = usize::from(variant - 4);
+                let bytes = [block3.0.to_be_bytes(), block4.0.to_be_bytes()];
+                for (offset, byte) in bytes.iter().flatten().enumerate() {
+                    self.provider_name[index * PROVIDER_SEGMENT_SIZE + offset] =
+                        to_basic_rds_char(*byte).unwrap_or(' ');
+                }
+                self.provider_segments
+                    .set_bit(index)
+                    .expect("Provider name segment should always be within range");
+                if self.provider_segments.all() {
+                    service.provider_name =
+                        Some(TmcProviderNameString::from_iter(self.provider_name));
+                }
+            }
+            6 => {
+                let network = other_network(service, block4.0);
+                let [af1, af2] = block3.0.to_be_bytes();
+                for frequency in [af1, af2].into_iter().filter_map(frequency_khz) {
+                    push_frequency(network, frequency);
+                }
+            }
+            7 => {
+                let network = other_network(service, block4.0);
+                let [_tuned, mapped] = block3.0.to_be_bytes();
+                if let Some(frequency) = frequency_khz(mapped) {
+                    push_frequency(network, frequency);
+                }
+            }
+            8 => {
+                other_network(service, block3.0);
+                other_network(service, block4.0);
+            }
+            9 => {
+                let network = other_network(service, block4.0);
+                network.location_table = Some(((block3.0 >> 10) & LTN_BITMASK) as u8);
+                network.scope = Some(scope(block3.0 >> 6));
+                network.service_id = Some((block3.0 & SID_BITMASK) as u8);
+            }
+            _ => {}
+        }
+    }
+
+    /// Push a TMC encryption administration group
+    pub fn push_encryption_administration(&mut self, block3: &Block3, block4: &Block4) {
+        // Only variant 0 is defined
+        if block3.0 >> ENCRYPTION_VARIANT_SHIFT != 0 {
+            return;
+        }
+        let service = self.service.get_or_insert_with(TmcService::default);
+        let key_table = match (block3.0 >> ENCRYPTION_TEST_SHIFT) & ENCRYPTION_TEST_BITMASK {
+            0 => TmcKeyTableSelector::Service,
+            _ => TmcKeyTableSelector::Test,
+        };
+        service.encryption = Some(TmcEncryption {
+            encryption_id: (block3.0 & ENCID_BITMASK) as u8,
+            key_table,
+            service_id: ((block3.0 >> 5) & SID_BITMASK) as u8,
+            location_table: ((block4.0 >> LTNBE_SHIFT) & LTN_BITMASK) as u8,
+        });
+    }
+
+    /// Returns the service description once any system or tuning information is received
+    pub fn service(&self) -> Option<&TmcService> {
+        self.service.as_ref()
+    }
+
+    pub fn reset(&mut self) {
+        self.service = None;
+        self.provider_name = [' '; TMC_PROVIDER_NAME_LENGTH];
+        self.provider_segments.reset();
+    }
+}
+
+/// Decode the 4-bit message geographical scope from the lowest bits
+fn scope(bits: u16) -> TmcScope {
+    TmcScope {
+        international: bits & 0x8 != 0,
+        national: bits & 0x4 != 0,
+        regional: bits & 0x2 != 0,
+        urban: bits & 0x1 != 0,
+    }
+}
+
+/// Convert an FM frequency code to kHz
+fn frequency_khz(code: u8) -> Option<u32> {
+    match code {
+        1..=FM_CODE_MAX => Some(FM_BASE_KHZ + u32::from(code) * FM_SPACING_KHZ),
+        _ => None,
+    }
+}
+
+fn push_frequency(network: &mut TmcOtherNetwork, frequency: u32) {
+    if !network.frequencies.contains(&frequency) {
+        let _ = network.frequencies.push(frequency);
+    }
+}
+
+/// Returns the other network with the PI, adding it if it is not known yet.
+///
+/// The last network is replaced once the list is full so that the caller
+/// always gets an entry to update.
+fn other_network(service: &mut TmcService, pi: u16) -> &mut TmcOtherNetwork {
+    let networks = &mut service.other_networks;
+    let index = match networks.iter().position(|network| network.pi == pi) {
+        Some(index) => index,
+        None => {
+            if networks.is_full() {
+                networks.pop();
+            }
+            let _ = networks.push(TmcOtherNetwork::new(pi));
+            networks.len() - 1
+        }
+    };
+    &mut networks[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_information() {
+        let mut decoder = ServiceDecoder::new();
+        assert_eq!(decoder.service(), None);
+        // Variant 0: LTN 12, AFI, basic mode, international and urban scope
+        decoder.push_system_information((12 << 6) | 0x20 | 0x9);
+        // Variant 1: gap 5 groups, SID 34
+        decoder.push_system_information((1 << 14) | (1 << 12) | (34 << 6));
+        let service = decoder.service().unwrap();
+        assert_eq!(service.location_table, 12);
+        assert!(service.alternative_frequencies);
+        assert!(!service.enhanced_mode);
+        assert_eq!(
+            service.scope,
+            TmcScope {
+                international: true,
+                national: false,
+                regional: false,
+                urban: true,
+            }
+        );
+        assert_eq!(service.gap, Some(5));
+        assert_eq!(service.service_id, Some(34));
+    }
+
+    #[test]
+    fn test_provider_name() {
+        let mut decoder = ServiceDecoder::new();
+        decoder.push_tuning_information(4, &Block3(0x5452), &Block4(0x4146));
+        assert_eq!(decoder.service().unwrap().provider_name, None);
+        decoder.push_tuning_information(5, &Block3(0x4649), &Block4(0x4320));
+        assert_eq!(
+            decoder.service().unwrap().provider_name.as_deref(),
+            Some("TRAFFIC ")
+        );
+    }
+
+    #[test]
+    fn test_other_networks() {
+        let mut decoder = ServiceDecoder::new();
+        // Variant 6: 87.6 MHz and 107.9 MHz for PI 0xD301
+        decoder.push_tuning_information(6, &Block3(0x01CC), &Block4(0xD301));
+        // Variant 7: mapped to 90.0 MHz for PI 0xD301
+        decoder.push_tuning_information(7, &Block3(0x0119), &Block4(0xD301));
+        // Variant 9: LTN 1, national scope, SID 2 for PI 0xD302
+        decoder.push_tuning_information(9, &Block3((1 << 10) | (0x4 << 6) | 2), &Block4(0xD302));
+
+        let networks = &decoder.service().unwrap().other_networks;
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].pi, 0xD301);
+        assert_eq!(
+            networks[0].frequencies.as_slice(),
+            &[87_600, 107_900, 90_000]
+        );
+        assert_eq!(networks[1].pi, 0xD302);
+        assert_eq!(networks[1].location_table, Some(1));
+        assert_eq!(networks[1].service_id, Some(2));
+        assert!(networks[1].scope.unwrap().national);
+    }
+
+    #[test]
+    fn test_encryption_administration() {
+        let mut decoder = ServiceDecoder::new();
+        // Test mode, SID 34, ENCID 7, LTNBE 12
+        decoder.push_encryption_administration(
+            &Block3((0x3 << 11) | (34 << 5) | 7),
+            &Block4(12 << 10),
+        );
+        assert_eq!(
+            decoder.service().unwrap().encryption,
+            Some(TmcEncryption {
+                encryption_id: 7,
+                key_table: TmcKeyTableSelector::Test,
+                service_id: 34,
+                location_table: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut decoder = ServiceDecoder::new();
+        decoder.push_system_information(12 << 6);
+        decoder.reset();
+        assert_eq!(decoder.service(), None);
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{
+    decoder::tmc_decoder::events::{lookup_event, TMC_CANCELLATION_EVENT},
+    types::{TmcDirection, TmcDurationType, TmcMessage},
+};
+
+/// Number of seconds in a day
+const DAY: u64 = 24 * 60 * 60;
+
+/// Time for which a message persists without being repeated
+#[derive(Clone, Copy, Debug)]
+enum Persistence {
+    /// Number of seconds
+    Seconds(u64),
+    /// Until the given midnight, 1 being the end of the current day
+    Midnight(u64),
+}
+
+/// Persistence of dynamic events for duration codes 0 to 7
+const DYNAMIC_PERSISTENCE: [Persistence; 8] = [
+    Persistence::Seconds(15 * 60),
+    Persistence::Seconds(15 * 60),
+    Persistence::Seconds(30 * 60),
+    Persistence::Seconds(60 * 60),
+    Persistence::Seconds(2 * 60 * 60),
+    Persistence::Seconds(3 * 60 * 60),
+    Persistence::Seconds(4 * 60 * 60),
+    Persistence::Midnight(1),
+];
+
+/// Persistence of longer lasting events for duration codes 0 to 7
+const LONGER_LASTING_PERSISTENCE: [Persistence; 8] = [
+    Persistence::Seconds(60 * 60),
+    Persistence::Seconds(2 * 60 * 60),
+    Persistence::Midnight(1),
+    Persistence::Midnight(2),
+    Persistence::Midnight(2),
+    Persistence::Midnight(2),
+    Persistence::Midnight(2),
+    Persistence::Midnight(2),
+];
+
+/// Returns the update class of an event code.
+pub type UpdateClassFn = fn(u16) -> u16;
+
+/// Update classes from the built-in event list, which range from 1 to 39.
+///
+/// Event codes missing from the built-in subset of the event list are treated
+/// as their own update class, so they only replace repetitions of themselves.
+fn event_list_update_class(event: u16) -> u16 {
+    /// Offset keeping unknown event codes clear of the event list update classes
+    const UNKNOWN_CLASS_OFFSET: u16 = 0x100;
+
+    match lookup_event(event) {
+        Some(info) => info.update_class.into(),
+        None => UNKNOWN_CLASS_OFFSET + event,
+    }
+}
+
+/// Message held by a [`TmcMessageStore`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TmcStoredMessage {
+    pub message: TmcMessage,
+    /// Time at which the message was last received
+    pub received: u64,
+    /// Time at which the message expires
+    pub expires: u64,
+}
+
+/// Store of active TMC messages following the ALERT-C message management rules.
+///
+/// - Repetitions of a message refresh its persistence.
+/// - A message replaces an older one with the same location, direction and update class.
+/// - [`TMC_CANCELLATION_EVENT`] removes the messages for the location and direction.
+/// - Messages expire once the persistence of their duration code has elapsed,
+///   using the dynamic or longer lasting persistence of [`TmcMessage::duration_type`].
+///
+/// Times are seconds supplied by the caller. The epoch must be aligned to local
+/// midnight (e.g. Unix time shifted by the local time zone offset) for messages
+/// to persist until the end of the day.
+///
+/// The message expiring first is dropped once `N` messages are stored.
+#[derive(Debug)]
+pub struct TmcMessageStore<const N: usize> {
+    messages: heapless::Vec<TmcStoredMessage, N>,
+    update_class: UpdateClassFn,
+}
+
+impl<const N: usize> TmcMessageStore<N> {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self {
+            messages: heapless::Vec::new(),
+            update_class: event_list_update_class,
+        }
+    }
+
+    /// Set the function used to look up the update class of an event code.
+    ///
+    /// By default the update class from the built-in event list is used. That
+    /// list is only a subset of ISO 14819-2, so a function covering the full
+    /// event list is needed for the replacement rules to apply to every event.
+    pub fn set_update_class(&mut self, update_class: UpdateClassFn) {
+        self.update_class = update_class;
+    }
+
+    /// Apply a received message to the store.
+    pub fn push(&mut self, message: TmcMessage, now: u64) {
+        self.expire(now);
+
+        if message.event == TMC_CANCELLATION_EVENT {
+            self.messages.retain(|stored| {
+                !Self::same_place(&stored.message, message.location, message.direction)
+            });
+            return;
+        }
+
+        let update_class = self.update_class;
+        let class = update_class(message.event);
+        self.messages.retain(|stored| {
+            !(Self::same_place(&stored.message, message.location, message.direction)
+                && update_class(stored.message.event) == class)
+        });
+
+        if self.messages.is_full() {
+            let soonest = self
+                .messages
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, stored)| stored.expires)
+                .map(|(index, _)| index);
+            if let Some(index) = soonest {
+                self.messages.swap_remove(index);
+            }
+        }
+
+        let expires = Self::expiry(&message, now);
+        let _ = self.messages.push(TmcStoredMessage {
+            message,
+            received: now,
+            expires,
+        });
+    }
+
+    /// Remove all messages that have expired by `now`
+    pub fn expire(&mut self, now: u64) {
+        self.messages.retain(|stored| stored.expires > now);
+    }
+
+    /// Returns the messages that are active at `now`
+    pub fn active(&self, now: u64) -> impl Iterator<Item = &TmcStoredMessage> {
+        self.messages
+            .iter()
+            .filter(move |stored| stored.expires > now)
+    }
+
+    /// Remove all messages
+    ///
+    /// This should be called after tuning to a different TMC service.
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    fn same_place(message: &TmcMessage, location: u16, direction: TmcDirection) -> bool {
+        message.location == location && message.direction == direction
+    }
+
+    fn expiry(message: &TmcMessage, now: u64) -> u64 {
+        let table = match message.duration_type() {
+            TmcDurationType::Dynamic => &DYNAMIC_PERSISTENCE,
+            TmcDurationType::LongerLasting => &LONGER_LASTING_PERSISTENCE,
+        };
+        match table[usize::from(message.duration) % table.len()] {
+            Persistence::Seconds(seconds) => now.saturating_add(seconds),
+            Persistence::Midnight(days) => (now / DAY + days).saturating_mul(DAY),
+        }
+    }
+}
+
+impl<const N: usize> Default for TmcMessageStore<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TmcLabel, TmcLabelList};
+
+    fn message(event: u16, location: u16, duration: u8) -> TmcMessage {
+        TmcMessage {
+            event,
+            location,
+            direction: TmcDirection::Positive,
+            extent: 1,
+            duration,
+            diversion: false,
+            labels: TmcLabelList::new(),
+        }
+    }
+
+    #[test]
+    fn test_repetition_refreshes_persistence() {
+        let mut store = TmcMessageStore::<4>::new();
+        store.push(message(101, 1000, 1), 0);
+        store.push(message(101, 1000, 1), 600);
+        let active: Vec<_> = store.active(600).collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].expires, 600 + 15 * 60);
+    }
+
+    #[test]
+    fn test_update_replaces_same_class() {
+        let mut store = TmcMessageStore::<4>::new();
+        store.set_update_class(|event| event / 100);
+        store.push(message(101, 1000, 1), 0);
+        store.push(message(102, 1000, 1), 10);
+        store.push(message(201, 1000, 1), 20);
+        let events: Vec<_> = store
+            .active(20)
+            .map(|stored| stored.message.event)
+            .collect();
+        assert_eq!(events, vec![102, 201]);
+    }
+
+    #[test]
+    fn test_update_with_event_list() {
+        let mut store = TmcMessageStore::<4>::new();
+        // Stationary traffic is replaced by slow traffic (both update class 1)
+        store.push(message(101, 1000, 1), 0);
+        store.push(message(115, 1000, 1), 10);
+        let events: Vec<_> = store
+            .active(10)
+            .map(|stored| stored.message.event)
+            .collect();
+        assert_eq!(events, vec![115]);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let mut store = TmcMessageStore::<4>::new();
+        store.push(message(101, 1000, 1), 0);
+        store.push(message(101, 2000, 1), 0);
+        store.push(message(TMC_CANCELLATION_EVENT, 1000, 0), 10);
+        let locations: Vec<_> = store
+            .active(10)
+            .map(|stored| stored.message.location)
+            .collect();
+        assert_eq!(locations, vec![2000]);
+    }
+
+    #[test]
+    fn test_cancellation_is_not_an_event_list_entry() {
+        // Cancellations are identified by their code alone
+        assert_eq!(lookup_event(TMC_CANCELLATION_EVENT), None);
+        let mut store = TmcMessageStore::<4>::new();
+        store.push(message(701, 1000, 3), 0);
+        store.push(message(TMC_CANCELLATION_EVENT, 1000, 0), 10);
+        assert_eq!(store.active(10).count(), 0);
+    }
+
+    #[test]
+    fn test_expiry_saturates() {
+        let mut store = TmcMessageStore::<4>::new();
+        store.push(message(101, 1000, 6), u64::MAX - 10);
+        store.push(message(701, 2000, 7), u64::MAX - 10);
+        assert_eq!(store.active(u64::MAX - 1).count(), 2);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut store = TmcMessageStore::<4>::new();
+        store.push(message(101, 1000, 2), 0);
+        store.push(message(101, 2000, 7), 3 * 60 * 60);
+        assert_eq!(store.active(30 * 60).count(), 1);
+        assert_eq!(store.active(DAY - 1).count(), 1);
+        assert_eq!(store.active(DAY).count(), 0);
+    }
+
+    #[test]
+    fn test_longer_lasting_expiry() {
+        let mut store = TmcMessageStore::<4>::new();
+        // Roadworks are longer lasting
+        store.push(message(701, 1000, 0), 0);
+        store.push(message(701, 2000, 2), 0);
+        store.push(message(701, 3000, 4), 0);
+        let expires: Vec<_> = store.active(0).map(|stored| stored.expires).collect();
+        assert_eq!(expires, vec![60 * 60, DAY, 2 * DAY]);
+
+        // Control code 3 makes stationary traffic longer lasting
+        let mut stationary = message(101, 4000, 3);
+        let _ = stationary.labels.push(TmcLabel::ControlCode(3));
+        store.clear();
+        store.push(stationary, 10);
+        assert_eq!(store.active(10).next().unwrap().expires, 2 * DAY);
+    }
+
+    #[test]
+    fn test_full_store_drops_soonest_expiry() {
+        let mut store = TmcMessageStore::<2>::new();
+        store.push(message(101, 1000, 6), 0);
+        store.push(message(101, 2000, 1), 0);
+        store.push(message(101, 3000, 3), 0);
+        let mut locations: Vec<_> = store
+            .active(0)
+            .map(|stored| stored.message.location)
+            .collect();
+        locations.sort();
+        assert_eq!(locations, vec![1000, 3000]);
+    }
+}
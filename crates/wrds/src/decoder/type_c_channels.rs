@@ -0,0 +1,76 @@
+use crate::types::{TypeCGroup, TYPE_C_CHANNELS};
+
+/// AIDs assigned to the ODA channels of type C groups by channel 0
+#[derive(Debug)]
+pub struct TypeCChannels {
+    aids: [Option<u16>; TYPE_C_CHANNELS],
+}
+
+impl TypeCChannels {
+    pub fn new() -> Self {
+        Self {
+            aids: [None; TYPE_C_CHANNELS],
+        }
+    }
+
+    /// Push a type C group, returning true if it was a channel assignment
+    pub fn push_group(&mut self, group: &TypeCGroup) -> bool {
+        let Some((aid, channel)) = group.channel_assignment() else {
+            return false;
+        };
+        // Channel 0 is reserved for the assignments
+        if channel != 0 {
+            self.aids[usize::from(channel)] = Some(aid);
+        }
+        true
+    }
+
+    /// Returns the AID assigned to the channel
+    pub fn aid(&self, channel: u8) -> Option<u16> {
+        self.aids.get(usize::from(channel)).copied().flatten()
+    }
+
+    pub fn reset(&mut self) {
+        self.aids = [None; TYPE_C_CHANNELS];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FunctionHeader;
+
+    #[test]
+    fn test_assignment() {
+        let mut channels = TypeCChannels::new();
+        // AID 0x1234 on channel 5
+        let assignment = TypeCGroup::new(1, [0x0012, 0x3405, 0x0000, 0x0000]);
+        assert_eq!(
+            assignment.function_header,
+            FunctionHeader::Oda { channel: 0 }
+        );
+        assert!(channels.push_group(&assignment));
+        assert_eq!(channels.aid(5), Some(0x1234));
+        assert_eq!(channels.aid(6), None);
+
+        let data = TypeCGroup::new(1, [0x0511, 0x2233, 0x4455, 0x6677]);
+        assert_eq!(data.function_header, FunctionHeader::Oda { channel: 5 });
+        assert_eq!(data.data, [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77]);
+        assert!(!channels.push_group(&data));
+
+        channels.reset();
+        assert_eq!(channels.aid(5), None);
+    }
+
+    #[test]
+    fn test_function_header() {
+        assert_eq!(
+            TypeCGroup::new(2, [0x8A00, 0, 0, 0]).function_header,
+            FunctionHeader::Rft { pipe: 0xA }
+        );
+        assert_eq!(
+            TypeCGroup::new(2, [0x4000, 0, 0, 0]).function_header,
+            FunctionHeader::Reserved(0x40)
+        );
+    }
+}
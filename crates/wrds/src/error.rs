@@ -8,6 +8,8 @@ pub enum Error {
     InvalidInput { field: &'static str, value: u16 },
     // #[error("Unimplemented RDS Group Type: {:?}", 0.0)]
     Unimplemented(GroupType),
+    // #[error("Exceeded maximum number of ODA handlers")]
+    MaxOdaHandlersExceeded,
     // #[error("Unknown error")]
     Unknown,
 }
@@ -21,6 +23,9 @@ impl fmt::Display for Error {
             Error::Unimplemented(group) => {
                 write!(f, "Unimplemented RDS Group Type: {:?}", group.0)
             }
+            Error::MaxOdaHandlersExceeded => {
+                write!(f, "Exceeded maximum number of ODA handlers")
+            }
             Error::Unknown => write!(f, "Unknown error"),
         }
     }
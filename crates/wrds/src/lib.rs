@@ -1,14 +1,58 @@
 #![deny(unsafe_code)]
-#![cfg_attr(all(not(test), not(feature = "fuzzing")), no_std)]
+#![cfg_attr(all(not(test), not(feature = "fuzzing"), not(feature = "std")), no_std)]
 
 mod decoder;
-pub use decoder::Decoder;
+pub use decoder::{
+    char_voter::MAX_TEXT_VOTES,
+    ews_decoder::EmergencyWarningCallback,
+    in_house_decoder::InHouseCallback,
+    oda_handler::{OdaGroup, OdaHandler, UnknownOdaCallback, MAX_ODA_HANDLERS},
+    oda_identifier::{
+        lookup_aid, registered_odas, OdaInfo, DEFAULT_ODA_CAPACITY, DEFAULT_ODA_TIMEOUT,
+        MAX_ODA_APPS,
+    },
+    rds_charset::{to_rds_char, RdsCharError, RdsCharsetDecoder, RdsCharsetEncoder},
+    tdc_decoder::{DEFAULT_TDC_BUFFER_SIZE, MAX_TDC_BUFFER_SIZE, TDC_CHANNELS},
+    tmc_decoder::events::{interpret_quantifier, lookup_event, TMC_CANCELLATION_EVENT},
+    tmc_store::{TmcMessageStore, TmcStoredMessage, UpdateClassFn},
+    Decoder, MAX_EVENTS,
+};
 
 mod error;
 pub use error::Error;
 
+#[cfg(feature = "std")]
+mod location_table;
+#[cfg(feature = "std")]
+pub use location_table::{
+    LocationTableError, TmcLocation, TmcLocationChain, TmcLocationKind, TmcLocationTable,
+};
+
+#[cfg(all(feature = "std", feature = "unstable"))]
+mod rft;
+#[cfg(all(feature = "std", feature = "unstable"))]
+pub use rft::{RftDecoder, RftFile, MAX_RFT_FILE_SIZE, RFT_PIPES, RFT_SEGMENT_SIZE};
+
 mod types;
 pub use types::{
-    Message, Metadata, ProgrammeIdentifier, ProgrammeType, RadioText, RadioTextPlusContentType,
-    RadioTextPlusTag, TrafficProgram,
+    Block1, Block2, Block3, Block4, DabLinkage, DabLinkageList, DabMode, DabServiceIdList,
+    EmergencyWarning, Event, GroupType, GroupVariant, InHouseData, LinkageInfo,
+    LinkedProgrammeList, Message, Metadata, NetworkLinkage, NetworkLinkageList, OdaAnnouncement,
+    PagingInfo, ProgrammeIdentifier, ProgrammeType, ProgrammeTypeName, ProgrammeTypeNameString,
+    RadioText, RadioTextPlusContentType, RadioTextPlusTag, RdsCodeTable, TmcDirection,
+    TmcDurationType, TmcEncryption, TmcEncryptionKey, TmcEventInfo, TmcEventNature,
+    TmcFrequencyList, TmcKeyTable, TmcKeyTableSelector, TmcLabel, TmcLabelList, TmcMessage,
+    TmcOtherNetwork, TmcOtherNetworkList, TmcProviderNameString, TmcQuantifierType, TmcQuantity,
+    TmcScope, TmcService, TrafficAnnouncement, TrafficProgram, MAX_DAB_LINKAGES, MAX_DAB_SERVICES,
+    MAX_LINKED_NETWORKS, MAX_TMC_FREQUENCIES, MAX_TMC_LABELS, MAX_TMC_OTHER_NETWORKS,
+    TMC_KEY_TABLE_SIZE, TMC_PROVIDER_NAME_LENGTH,
+};
+#[cfg(feature = "unstable")]
+pub use types::{
+    EnhancedPaging, PagerAddress, PagingContent, PagingDigitString, PagingMessage, PagingNetwork,
+    PagingTextString, MAX_PAGING_DIGITS, MAX_PAGING_SEGMENTS, MAX_PAGING_TEXT_LENGTH,
+};
+#[cfg(feature = "unstable")]
+pub use types::{
+    FunctionHeader, TypeCGroup, RDS2_STREAMS, RFT_AID, TYPE_C_CHANNELS, TYPE_C_DATA_SIZE,
 };
@@ -0,0 +1,345 @@
+//! TMC location tables in the Location Table Exchange Format (LTEF).
+//!
+//! A location table is a directory of semicolon separated `.DAT` files whose
+//! first line names the columns. The following files are used:
+//!
+//! - `NAMES.DAT` (required)
+//! - `POINTS.DAT` (required)
+//! - `ROADS.DAT`, `SEGMENTS.DAT`, `POFFSETS.DAT` and `SOFFSETS.DAT` (optional)
+//!
+//! Area locations are not loaded.
+
+use std::{collections::HashMap, fmt, io, path::Path};
+
+use crate::types::{TmcDirection, TmcMessage};
+
+use self::ltef::{Column, LtefFile, Record};
+
+mod ltef;
+
+/// Error loading an LTEF location table
+#[derive(Debug)]
+pub enum LocationTableError {
+    // #[error("Failed to read \"{file}\": {source}")]
+    Io {
+        file: &'static str,
+        source: io::Error,
+    },
+    // #[error("Missing column \"{column}\" in \"{file}\"")]
+    MissingColumn {
+        file: &'static str,
+        column: &'static str,
+    },
+    // #[error("Invalid value for column \"{column}\" in \"{file}\" line {line}")]
+    InvalidValue {
+        file: &'static str,
+        line: usize,
+        column: &'static str,
+    },
+}
+
+impl fmt::Display for LocationTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocationTableError::Io { file, source } => {
+                write!(f, "Failed to read \"{file}\": {source}")
+            }
+            LocationTableError::MissingColumn { file, column } => {
+                write!(f, "Missing column \"{column}\" in \"{file}\"")
+            }
+            LocationTableError::InvalidValue { file, line, column } => {
+                write!(
+                    f,
+                    "Invalid value for column \"{column}\" in \"{file}\" line {line}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LocationTableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LocationTableError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of a TMC location
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcLocationKind {
+    Road,
+    Segment,
+    Point,
+}
+
+/// Location from a TMC location table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TmcLocation {
+    /// Location code (LCD)
+    pub code: u16,
+    pub kind: TmcLocationKind,
+    /// Location type (TCD)
+    pub location_type: u8,
+    /// Location subtype (STCD)
+    pub subtype: u8,
+    /// Road number of roads and segments, e.g. "A1"
+    pub road_number: Option<String>,
+    /// Junction number of points
+    pub junction_number: Option<String>,
+    pub road_name: Option<String>,
+    pub first_name: Option<String>,
+    pub second_name: Option<String>,
+    /// Area the location lies in (POL_LCD)
+    pub area: Option<u16>,
+    /// Road the location belongs to (ROA_LCD)
+    pub road: Option<u16>,
+    /// Segment the location belongs to (SEG_LCD)
+    pub segment: Option<u16>,
+    /// Next location in the negative direction
+    pub negative_offset: Option<u16>,
+    /// Next location in the positive direction
+    pub positive_offset: Option<u16>,
+}
+
+impl TmcLocation {
+    /// Returns the next location in the direction
+    pub fn offset(&self, direction: TmcDirection) -> Option<u16> {
+        match direction {
+            TmcDirection::Positive => self.positive_offset,
+            TmcDirection::Negative => self.negative_offset,
+        }
+    }
+}
+
+/// Locations affected by a TMC message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TmcLocationChain<'a> {
+    /// Locations from the primary location to the secondary location
+    pub locations: Vec<&'a TmcLocation>,
+    /// Segments covered by the locations, in the order of the chain
+    pub segments: Vec<&'a TmcLocation>,
+    /// Road of the primary location
+    pub road: Option<&'a TmcLocation>,
+    /// Whether the secondary location was reached, `false` if the chain ended early
+    pub complete: bool,
+}
+
+impl<'a> TmcLocationChain<'a> {
+    /// Returns the primary location
+    pub fn primary(&self) -> &'a TmcLocation {
+        self.locations[0]
+    }
+
+    /// Returns the last location of the chain, which is the secondary location if complete
+    pub fn secondary(&self) -> &'a TmcLocation {
+        self.locations[self.locations.len() - 1]
+    }
+}
+
+/// TMC location table loaded from LTEF files
+#[derive(Clone, Debug, Default)]
+pub struct TmcLocationTable {
+    table_number: Option<u8>,
+    locations: HashMap<u16, TmcLocation>,
+}
+
+impl TmcLocationTable {
+    /// Load a location table from a directory of LTEF files
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, LocationTableError> {
+        let dir = dir.as_ref();
+        let names = read_names(&LtefFile::read_required(dir, "NAMES.DAT")?)?;
+
+        let mut table = Self::default();
+        if let Some(file) = LtefFile::read(dir, "ROADS.DAT")? {
+            table.insert_locations(&file, TmcLocationKind::Road, &names)?;
+        }
+        if let Some(file) = LtefFile::read(dir, "SEGMENTS.DAT")? {
+            table.insert_locations(&file, TmcLocationKind::Segment, &names)?;
+        }
+        let points = LtefFile::read_required(dir, "POINTS.DAT")?;
+        table.insert_locations(&points, TmcLocationKind::Point, &names)?;
+
+        for offsets in ["POFFSETS.DAT", "SOFFSETS.DAT"] {
+            if let Some(file) = LtefFile::read(dir, offsets)? {
+                table.insert_offsets(&file)?;
+            }
+        }
+        Ok(table)
+    }
+
+    /// Returns the location table number (TABCD)
+    pub fn table_number(&self) -> Option<u8> {
+        self.table_number
+    }
+
+    /// Returns the location with the location code
+    pub fn location(&self, code: u16) -> Option<&TmcLocation> {
+        self.locations.get(&code)
+    }
+
+    /// Returns the number of locations in the table
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Resolve a primary location and extent into the chain of affected locations.
+    ///
+    /// The chain follows `extent` offsets from the primary location in the
+    /// direction of queue growth. Returns `None` if the primary location is unknown.
+    pub fn resolve(
+        &self,
+        location: u16,
+        direction: TmcDirection,
+        extent: u8,
+    ) -> Option<TmcLocationChain<'_>> {
+        let primary = self.location(location)?;
+
+        let mut locations = vec![primary];
+        let mut current = primary;
+        for _ in 0..extent {
+            match current
+                .offset(direction)
+                .and_then(|code| self.location(code))
+            {
+                Some(next) => {
+                    locations.push(next);
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        let complete = locations.len() == usize::from(extent) + 1;
+
+        let mut segments: Vec<&TmcLocation> = Vec::new();
+        for location in &locations {
+            let segment = match location.kind {
+                TmcLocationKind::Segment => Some(*location),
+                _ => location.segment.and_then(|code| self.location(code)),
+            };
+            if let Some(segment) = segment {
+                if !segments.iter().any(|known| known.code == segment.code) {
+                    segments.push(segment);
+                }
+            }
+        }
+
+        let road = match primary.kind {
+            TmcLocationKind::Road => Some(primary),
+            _ => primary
+                .road
+                .or_else(|| segments.first().and_then(|segment| segment.road))
+                .and_then(|code| self.location(code)),
+        };
+
+        Some(TmcLocationChain {
+            locations,
+            segments,
+            road,
+            complete,
+        })
+    }
+
+    /// Resolve the locations affected by a TMC message
+    pub fn resolve_message(&self, message: &TmcMessage) -> Option<TmcLocationChain<'_>> {
+        self.resolve(message.location, message.direction, message.extent)
+    }
+
+    fn insert_locations(
+        &mut self,
+        file: &LtefFile,
+        kind: TmcLocationKind,
+        names: &HashMap<u32, String>,
+    ) -> Result<(), LocationTableError> {
+        let table = file.column("TABCD")?;
+        let code = file.column("LCD")?;
+        let location_type = file.column("TCD")?;
+        let subtype = file.column("STCD")?;
+        let road_name = file.column("RNID")?;
+        let first_name = file.column("N1ID")?;
+        let second_name = file.column("N2ID")?;
+        let area = file.column("POL_LCD")?;
+        let road_number = file.column("ROADNUMBER").ok();
+        let junction_number = file.column("JUNCTIONNUMBER").ok();
+        let road = file.column("ROA_LCD").ok();
+        let segment = file.column("SEG_LCD").ok();
+
+        let name = |record: &Record, column: Column| -> Result<Option<String>, _> {
+            Ok(record
+                .number::<u32>(column)?
+                .and_then(|id| names.get(&id).cloned()))
+        };
+        let text = |record: &Record, column: Option<Column>| {
+            column
+                .and_then(|column| record.text(column))
+                .map(str::to_string)
+        };
+        let reference = |record: &Record, column: Option<Column>| match column {
+            Some(column) => record.location(column),
+            None => Ok(None),
+        };
+
+        for record in file.records() {
+            let Some(code) = record.location(code)? else {
+                continue;
+            };
+            if self.table_number.is_none() {
+                self.table_number = record.number(table)?;
+            }
+            let location = TmcLocation {
+                code,
+                kind,
+                location_type: record.number(location_type)?.unwrap_or_default(),
+                subtype: record.number(subtype)?.unwrap_or_default(),
+                road_number: text(&record, road_number),
+                junction_number: text(&record, junction_number),
+                road_name: name(&record, road_name)?,
+                first_name: name(&record, first_name)?,
+                second_name: name(&record, second_name)?,
+                area: record.location(area)?,
+                road: reference(&record, road)?,
+                segment: reference(&record, segment)?,
+                negative_offset: None,
+                positive_offset: None,
+            };
+            self.locations.insert(code, location);
+        }
+        Ok(())
+    }
+
+    fn insert_offsets(&mut self, file: &LtefFile) -> Result<(), LocationTableError> {
+        let code = file.column("LCD")?;
+        let negative = file.column("NEG_OFF_LCD")?;
+        let positive = file.column("POS_OFF_LCD")?;
+
+        for record in file.records() {
+            let Some(code) = record.location(code)? else {
+                continue;
+            };
+            if let Some(location) = self.locations.get_mut(&code) {
+                location.negative_offset = record.location(negative)?;
+                location.positive_offset = record.location(positive)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the names by name ID, keeping the first language listed for each name
+fn read_names(file: &LtefFile) -> Result<HashMap<u32, String>, LocationTableError> {
+    let id = file.column("NID")?;
+    let name = file.column("NAME")?;
+
+    let mut names = HashMap::new();
+    for record in file.records() {
+        if let (Some(id), Some(name)) = (record.number(id)?, record.text(name)) {
+            names.entry(id).or_insert_with(|| name.to_string());
+        }
+    }
+    Ok(names)
+}
@@ -0,0 +1,193 @@
+use std::{fs, io, path::Path, str::FromStr};
+
+use super::LocationTableError;
+
+/// Field separator of LTEF files
+const SEPARATOR: char = ';';
+
+/// Byte order mark at the start of some UTF-8 encoded files
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
+/// Contents of a single LTEF file, e.g. `POINTS.DAT`
+#[derive(Debug)]
+pub struct LtefFile {
+    name: &'static str,
+    columns: Vec<String>,
+    /// Records with their line number
+    records: Vec<(usize, Vec<String>)>,
+}
+
+impl LtefFile {
+    /// Read the file `name` from `dir`.
+    ///
+    /// Returns `None` if the file does not exist.
+    pub fn read(dir: &Path, name: &'static str) -> Result<Option<Self>, LocationTableError> {
+        match fs::read(dir.join(name)) {
+            Ok(bytes) => Ok(Some(Self::parse(name, &decode(bytes)))),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(LocationTableError::Io { file: name, source }),
+        }
+    }
+
+    /// Read the file `name` from `dir`, failing if it does not exist
+    pub fn read_required(dir: &Path, name: &'static str) -> Result<Self, LocationTableError> {
+        match fs::read(dir.join(name)) {
+            Ok(bytes) => Ok(Self::parse(name, &decode(bytes))),
+            Err(source) => Err(LocationTableError::Io { file: name, source }),
+        }
+    }
+
+    /// Parse the text of an LTEF file, whose first line names the columns
+    pub fn parse(name: &'static str, text: &str) -> Self {
+        let mut lines = text
+            .trim_start_matches(BYTE_ORDER_MARK)
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let columns = lines
+            .next()
+            .map(|(_, header)| split(header))
+            .unwrap_or_default();
+        let records = lines
+            .map(|(index, line)| (index + 1, split(line)))
+            .collect();
+
+        Self {
+            name,
+            columns,
+            records,
+        }
+    }
+
+    /// Returns the index of a column
+    pub fn column(&self, column: &'static str) -> Result<Column, LocationTableError> {
+        self.columns
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(column))
+            .map(|index| Column {
+                name: column,
+                index,
+            })
+            .ok_or(LocationTableError::MissingColumn {
+                file: self.name,
+                column,
+            })
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = Record<'_>> {
+        self.records.iter().map(|(line, fields)| Record {
+            file: self.name,
+            line: *line,
+            fields,
+        })
+    }
+}
+
+/// Column of an LTEF file
+#[derive(Clone, Copy, Debug)]
+pub struct Column {
+    name: &'static str,
+    index: usize,
+}
+
+/// Record (line) of an LTEF file
+#[derive(Debug)]
+pub struct Record<'a> {
+    file: &'static str,
+    line: usize,
+    fields: &'a [String],
+}
+
+impl Record<'_> {
+    /// Returns the text of a field, `None` if it is empty
+    pub fn text(&self, column: Column) -> Option<&str> {
+        self.fields
+            .get(column.index)
+            .map(String::as_str)
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Parse a numeric field, `None` if it is empty
+    pub fn number<T: FromStr>(&self, column: Column) -> Result<Option<T>, LocationTableError> {
+        self.text(column)
+            .map(|text| {
+                text.trim_start_matches('+')
+                    .parse()
+                    .map_err(|_| LocationTableError::InvalidValue {
+                        file: self.file,
+                        line: self.line,
+                        column: column.name,
+                    })
+            })
+            .transpose()
+    }
+
+    /// Parse a location code reference, where both empty fields and 0 mean no location
+    pub fn location(&self, column: Column) -> Result<Option<u16>, LocationTableError> {
+        Ok(self.number(column)?.filter(|&code| code != 0))
+    }
+}
+
+/// Decode the contents of an LTEF file.
+///
+/// Location tables are commonly distributed in ISO 8859-1, so text that is not
+/// valid UTF-8 is decoded as ISO 8859-1.
+fn decode(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|error| error.into_bytes().into_iter().map(char::from).collect())
+}
+
+fn split(line: &str) -> Vec<String> {
+    line.split(SEPARATOR)
+        .map(|field| field.trim().trim_matches('"').to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let file = LtefFile::parse(
+            "POFFSETS.DAT",
+            "\u{FEFF}CID;TABCD;LCD;NEG_OFF_LCD;POS_OFF_LCD\r\n58;1;10001;;10002\r\n\r\n",
+        );
+        let lcd = file.column("LCD").unwrap();
+        let negative = file.column("NEG_OFF_LCD").unwrap();
+        let positive = file.column("pos_off_lcd").unwrap();
+        let record = file.records().next().unwrap();
+        assert_eq!(record.number::<u16>(lcd).unwrap(), Some(10001));
+        assert_eq!(record.location(negative).unwrap(), None);
+        assert_eq!(record.location(positive).unwrap(), Some(10002));
+        assert_eq!(file.records().count(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let file = LtefFile::parse("POINTS.DAT", "CID;LCD\n58;A1\n");
+        assert!(matches!(
+            file.column("TCD"),
+            Err(LocationTableError::MissingColumn {
+                file: "POINTS.DAT",
+                column: "TCD"
+            })
+        ));
+        let lcd = file.column("LCD").unwrap();
+        assert!(matches!(
+            file.records().next().unwrap().number::<u16>(lcd),
+            Err(LocationTableError::InvalidValue {
+                file: "POINTS.DAT",
+                line: 2,
+                column: "LCD"
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decode_latin1() {
+        assert_eq!(decode(b"K\xF6ln".to_vec()), "Köln");
+        assert_eq!(decode("Köln".as_bytes().to_vec()), "Köln");
+    }
+}
@@ -0,0 +1,465 @@
+//! RDS2 file transfer (RFT), the Open Data Application with AID 0xFF7F.
+//!
+//! Files such as station logos are sent on one of 16 pipes. The file description
+//! is carried by the ODA groups assigned to the AID, usually on stream 0:
+//!
+//! - Block 2 ends with the pipe number (4 bits) and Block 3 starts with a 4-bit
+//!   variant code.
+//! - Variant 0: file version (3 bits), file ID (6 bits), CRC flag (1 bit) and the
+//!   file size in bytes (18 bits, ending in Block 4).
+//! - Variant 1: CRC mode (3 bits), chunk address (9 bits) and the CRC of the
+//!   chunk in Block 4. Mode 0 covers the whole file, modes 1 to 7 cover chunks of
+//!   2^(mode + 3) segments.
+//! - Variants 2 to 15: 28 bits of file-related data defined by the application.
+//!
+//! The file content is sent in type C groups on the additional streams, whose
+//! function header selects the pipe. The data bytes start with a toggle bit and
+//! the 15-bit segment address, followed by the 5 bytes of the segment. The toggle
+//! bit changes whenever the content of the pipe changes.
+//!
+//! The CRC is the CRC-16 of ITU-T X.25 without bit reflection, as used by DAB MOT.
+//!
+//! These layouts have not been checked against the RFT clauses of IEC 62106-2 nor
+//! against recordings of RFT broadcasts, which is why RFT is only built with the
+//! `unstable` feature. The tests only use group streams generated with the same
+//! layouts, so files sent by real stations may not be assembled.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::{
+    decoder::oda_handler::{OdaGroup, OdaHandler},
+    types::{FunctionHeader, GroupVariant, TypeCGroup, RFT_AID},
+};
+
+/// Number of RFT pipes
+pub const RFT_PIPES: usize = 16;
+
+/// Number of file bytes carried by each RFT data group
+pub const RFT_SEGMENT_SIZE: usize = 5;
+
+/// Maximum size of an RFT file in bytes, limited by the 15-bit segment address
+pub const MAX_RFT_FILE_SIZE: usize = (1 << 15) * RFT_SEGMENT_SIZE;
+
+const PIPE_BITMASK: u16 = 0xF;
+const VARIANT_SHIFT: usize = 12;
+const FILE_DESCRIPTION_VARIANT: u16 = 0;
+const CRC_VARIANT: u16 = 1;
+
+const TOGGLE_BITMASK: u8 = 0x80;
+const SEGMENT_MSB_BITMASK: u8 = 0x7F;
+
+/// File received completely over RFT
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RftFile {
+    pub pipe: u8,
+    /// File ID (6 bits)
+    pub file_id: u8,
+    /// File version (3 bits)
+    pub version: u8,
+    /// True if the file passed CRC verification
+    pub crc_verified: bool,
+    /// File-related data of variants 2 to 15 as (variant, 28-bit value), ordered by variant
+    pub application_data: Vec<(u8, u32)>,
+    pub data: Vec<u8>,
+}
+
+impl RftFile {
+    /// Returns the MIME type of the file detected from its content, if known
+    pub fn mime_type(&self) -> Option<&'static str> {
+        const SIGNATURES: [(&[u8], &str); 5] = [
+            (b"\x89PNG\r\n\x1A\n", "image/png"),
+            (b"\xFF\xD8\xFF", "image/jpeg"),
+            (b"GIF87a", "image/gif"),
+            (b"GIF89a", "image/gif"),
+            (b"<svg", "image/svg+xml"),
+        ];
+        if self.data.starts_with(b"RIFF") && self.data.get(8..12) == Some(b"WEBP") {
+            return Some("image/webp");
+        }
+        SIGNATURES
+            .iter()
+            .find(|(signature, _)| self.data.starts_with(signature))
+            .map(|(_, mime_type)| *mime_type)
+    }
+}
+
+/// File description from variant 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileDescription {
+    version: u8,
+    file_id: u8,
+    crc: bool,
+    size: usize,
+}
+
+impl FileDescription {
+    fn segments(&self) -> usize {
+        self.size.div_ceil(RFT_SEGMENT_SIZE)
+    }
+}
+
+/// Assembly state of a pipe
+#[derive(Debug, Default)]
+struct Pipe {
+    description: Option<FileDescription>,
+    crc_mode: Option<u8>,
+    /// CRC of each chunk by chunk address
+    crcs: HashMap<u16, u16>,
+    application_data: BTreeMap<u8, u32>,
+    toggle: Option<bool>,
+    data: Vec<u8>,
+    received: Vec<bool>,
+    missing: usize,
+    delivered: bool,
+}
+
+impl Pipe {
+    fn push_description(&mut self, description: FileDescription) {
+        if self.description == Some(description) {
+            return;
+        }
+        *self = Self {
+            description: Some(description),
+            application_data: core::mem::take(&mut self.application_data),
+            ..Self::default()
+        };
+        self.clear_data();
+    }
+
+    fn push_crc(&mut self, mode: u8, chunk: u16, crc: u16) {
+        if self.crc_mode != Some(mode) {
+            self.crc_mode = Some(mode);
+            self.crcs.clear();
+        }
+        self.crcs.insert(chunk, crc);
+    }
+
+    fn push_segment(&mut self, toggle: bool, segment: usize, bytes: &[u8; RFT_SEGMENT_SIZE]) {
+        let Some(description) = self.description else {
+            return;
+        };
+        if self.toggle.is_some_and(|current| current != toggle) {
+            self.clear_data();
+        }
+        self.toggle = Some(toggle);
+
+        let start = segment * RFT_SEGMENT_SIZE;
+        if start >= description.size || self.received[segment] {
+            return;
+        }
+        let end = (start + RFT_SEGMENT_SIZE).min(description.size);
+        self.data[start..end].copy_from_slice(&bytes[..end - start]);
+        self.received[segment] = true;
+        self.missing -= 1;
+    }
+
+    /// Returns the file once all segments are received and verified.
+    ///
+    /// Segments failing CRC verification are discarded so that they are received again.
+    fn complete(&mut self, pipe: u8) -> Option<RftFile> {
+        let description = self.description?;
+        if self.missing > 0 || self.delivered {
+            return None;
+        }
+        if description.crc && !self.verify() {
+            return None;
+        }
+        self.delivered = true;
+        Some(RftFile {
+            pipe,
+            file_id: description.file_id,
+            version: description.version,
+            crc_verified: description.crc,
+            application_data: self.application_data.clone().into_iter().collect(),
+            data: self.data.clone(),
+        })
+    }
+
+    /// Returns true if the CRCs of all chunks are known and match
+    fn verify(&mut self) -> bool {
+        let Some(mode) = self.crc_mode else {
+            return false;
+        };
+        let segments = self.received.len();
+        let chunk_segments = match mode {
+            0 => segments.max(1),
+            mode => 1 << (mode + 3),
+        };
+
+        let mut verified = true;
+        for (chunk, first) in (0..segments).step_by(chunk_segments).enumerate() {
+            let Some(expected) = u16::try_from(chunk)
+                .ok()
+                .and_then(|chunk| self.crcs.get(&chunk))
+            else {
+                verified = false;
+                continue;
+            };
+            let last = (first + chunk_segments).min(segments);
+            let bytes = first * RFT_SEGMENT_SIZE..(last * RFT_SEGMENT_SIZE).min(self.data.len());
+            if crc16(&self.data[bytes]) != *expected {
+                self.received[first..last].fill(false);
+                self.missing += last - first;
+                verified = false;
+            }
+        }
+        verified
+    }
+
+    fn clear_data(&mut self) {
+        let Some(description) = self.description else {
+            return;
+        };
+        self.data = vec![0; description.size];
+        self.received = vec![false; description.segments()];
+        self.missing = description.segments();
+        self.delivered = false;
+    }
+}
+
+/// Decoder for RDS2 file transfer (RFT).
+///
+/// Only built with the `unstable` feature, as the RFT layouts have not been checked
+/// against IEC 62106-2.
+///
+/// Register the decoder with [`crate::Decoder::register_oda_handler`] to receive
+/// the file descriptions and the type C groups of the RFT pipes. Complete files are
+/// retrieved through [`RftDecoder::poll_file`].
+#[derive(Debug)]
+pub struct RftDecoder {
+    pipes: [Pipe; RFT_PIPES],
+    files: VecDeque<RftFile>,
+}
+
+impl RftDecoder {
+    pub fn new() -> Self {
+        Self {
+            pipes: Default::default(),
+            files: VecDeque::new(),
+        }
+    }
+
+    /// Push a type C group, ignoring groups without an RFT function header
+    pub fn push_data_group(&mut self, group: &TypeCGroup) {
+        let FunctionHeader::Rft { pipe } = group.function_header else {
+            return;
+        };
+        let [address, address_lsb, segment @ ..] = group.data;
+        let toggle = address & TOGGLE_BITMASK != 0;
+        let address = (usize::from(address & SEGMENT_MSB_BITMASK) << 8) | usize::from(address_lsb);
+
+        self.pipes[usize::from(pipe)].push_segment(toggle, address, &segment);
+        self.complete(pipe);
+    }
+
+    /// Returns the oldest complete file, if any
+    pub fn poll_file(&mut self) -> Option<RftFile> {
+        self.files.pop_front()
+    }
+
+    pub fn reset(&mut self) {
+        self.pipes = Default::default();
+        self.files.clear();
+    }
+
+    fn complete(&mut self, pipe: u8) {
+        if let Some(file) = self.pipes[usize::from(pipe)].complete(pipe) {
+            self.files.push_back(file);
+        }
+    }
+}
+
+impl Default for RftDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OdaHandler for RftDecoder {
+    fn aid(&self) -> u16 {
+        RFT_AID
+    }
+
+    fn decode_group(&mut self, group: &OdaGroup) {
+        if group.group_variant != GroupVariant::A {
+            return;
+        }
+        let (Some(block3), Some(block4)) = (group.block3, group.block4) else {
+            return;
+        };
+        let pipe = (group.block2.0 & PIPE_BITMASK) as u8;
+        let data = block3.0 & 0xFFF;
+        let state = &mut self.pipes[usize::from(pipe)];
+        match block3.0 >> VARIANT_SHIFT {
+            FILE_DESCRIPTION_VARIANT => state.push_description(FileDescription {
+                version: (data >> 9) as u8,
+                file_id: ((data >> 3) & 0x3F) as u8,
+                crc: data & 0x4 != 0,
+                size: (usize::from(data & 0x3) << 16) | usize::from(block4.0),
+            }),
+            CRC_VARIANT => state.push_crc((data >> 9) as u8, data & 0x1FF, block4.0),
+            variant => {
+                let value = (u32::from(data) << 16) | u32::from(block4.0);
+                state.application_data.insert(variant as u8, value);
+            }
+        }
+        self.complete(pipe);
+    }
+
+    fn decode_type_c_group(&mut self, group: &TypeCGroup) {
+        self.push_data_group(group);
+    }
+}
+
+/// CRC-16 of ITU-T X.25 without bit reflection
+fn crc16(data: &[u8]) -> u16 {
+    const POLYNOMIAL: u16 = 0x1021;
+    let mut crc: u16 = 0xFFFF;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = match crc & 0x8000 {
+                0 => crc << 1,
+                _ => (crc << 1) ^ POLYNOMIAL,
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Block2, Block3, Block4, GroupType};
+
+    const CONTENT: &[u8] = b"Hello, RDS2 file transfer!";
+
+    fn description_group(pipe: u16, block3: u16, block4: u16) -> OdaGroup {
+        OdaGroup {
+            group_type: GroupType(12),
+            group_variant: GroupVariant::A,
+            block2: Block2(0xC000 | pipe),
+            block3: Some(Block3(block3)),
+            block4: Some(Block4(block4)),
+        }
+    }
+
+    fn data_group(pipe: u8, toggle: bool, segment: usize, content: &[u8]) -> TypeCGroup {
+        let mut bytes = [0; RFT_SEGMENT_SIZE];
+        let chunk = content
+            .chunks(RFT_SEGMENT_SIZE)
+            .nth(segment)
+            .unwrap_or_default();
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        let [address, address_lsb] = (segment as u16).to_be_bytes();
+        let address = address | (u8::from(toggle) << 7);
+        TypeCGroup {
+            stream: 1,
+            function_header: FunctionHeader::Rft { pipe },
+            data: [
+                address,
+                address_lsb,
+                bytes[0],
+                bytes[1],
+                bytes[2],
+                bytes[3],
+                bytes[4],
+            ],
+        }
+    }
+
+    fn push_content(decoder: &mut RftDecoder, pipe: u8, toggle: bool, content: &[u8]) {
+        for segment in 0..content.len().div_ceil(RFT_SEGMENT_SIZE) {
+            decoder.push_data_group(&data_group(pipe, toggle, segment, content));
+        }
+    }
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(crc16(b"123456789"), 0xD64E);
+    }
+
+    #[test]
+    fn test_file_without_crc() {
+        let mut decoder = RftDecoder::new();
+        // Version 1, file ID 5, no CRC
+        decoder.decode_group(&description_group(3, 0x0228, CONTENT.len() as u16));
+        decoder.decode_group(&description_group(3, 0x2012, 0x3456));
+        push_content(&mut decoder, 3, false, CONTENT);
+
+        let file = decoder.poll_file().unwrap();
+        assert_eq!(file.pipe, 3);
+        assert_eq!(file.file_id, 5);
+        assert_eq!(file.version, 1);
+        assert!(!file.crc_verified);
+        assert_eq!(file.application_data, [(2, 0x012_3456)]);
+        assert_eq!(file.data, CONTENT);
+        assert_eq!(file.mime_type(), None);
+
+        // The file is only delivered once
+        push_content(&mut decoder, 3, false, CONTENT);
+        assert_eq!(decoder.poll_file(), None);
+    }
+
+    #[test]
+    fn test_file_crc() {
+        let mut decoder = RftDecoder::new();
+        decoder.decode_group(&description_group(0, 0x022C, CONTENT.len() as u16));
+        decoder.decode_group(&description_group(0, 0x1000, crc16(CONTENT)));
+
+        // A corrupted segment is discarded after CRC verification
+        let mut corrupted = CONTENT.to_vec();
+        corrupted[7] ^= 0xFF;
+        push_content(&mut decoder, 0, false, &corrupted);
+        assert_eq!(decoder.poll_file(), None);
+
+        push_content(&mut decoder, 0, false, CONTENT);
+        let file = decoder.poll_file().unwrap();
+        assert!(file.crc_verified);
+        assert_eq!(file.data, CONTENT);
+    }
+
+    #[test]
+    fn test_chunk_crc() {
+        let content: Vec<u8> = (0..=255).collect();
+        let chunk_size = 16 * RFT_SEGMENT_SIZE;
+        let mut decoder = RftDecoder::new();
+        decoder.decode_group(&description_group(1, 0x0004, content.len() as u16));
+        push_content(&mut decoder, 1, true, &content);
+        // Waiting for the CRC of every chunk
+        for (chunk, bytes) in content.chunks(chunk_size).enumerate() {
+            assert_eq!(decoder.poll_file(), None);
+            let block3 = 0x1200 | chunk as u16;
+            decoder.decode_group(&description_group(1, block3, crc16(bytes)));
+        }
+        assert_eq!(decoder.poll_file().unwrap().data, content);
+    }
+
+    #[test]
+    fn test_toggle_restarts_file() {
+        let mut decoder = RftDecoder::new();
+        decoder.decode_group(&description_group(2, 0x0000, CONTENT.len() as u16));
+        decoder.push_data_group(&data_group(2, false, 0, b"xxxxx"));
+        for segment in 1..CONTENT.len().div_ceil(RFT_SEGMENT_SIZE) {
+            decoder.push_data_group(&data_group(2, true, segment, CONTENT));
+        }
+        assert_eq!(decoder.poll_file(), None);
+        decoder.push_data_group(&data_group(2, true, 0, CONTENT));
+        assert_eq!(decoder.poll_file().unwrap().data, CONTENT);
+    }
+
+    #[test]
+    fn test_mime_type() {
+        let mut file = RftFile {
+            pipe: 0,
+            file_id: 0,
+            version: 0,
+            crc_verified: false,
+            application_data: Vec::new(),
+            data: b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR".to_vec(),
+        };
+        assert_eq!(file.mime_type(), Some("image/png"));
+        file.data = b"RIFF\0\0\0\0WEBPVP8 ".to_vec();
+        assert_eq!(file.mime_type(), Some("image/webp"));
+    }
+}
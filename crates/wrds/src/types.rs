@@ -1,4 +1,41 @@
-use crate::error::Error;
+use crate::{
+    decoder::oda_identifier::{lookup_aid, OdaInfo},
+    error::Error,
+};
+
+mod dab;
+pub use dab::{
+    DabLinkage, DabLinkageList, DabMode, DabServiceIdList, MAX_DAB_LINKAGES, MAX_DAB_SERVICES,
+};
+
+mod linkage;
+pub use linkage::{
+    LinkageInfo, LinkedProgrammeList, NetworkLinkage, NetworkLinkageList, MAX_LINKED_NETWORKS,
+};
+
+mod paging;
+pub use paging::PagingInfo;
+#[cfg(feature = "unstable")]
+pub use paging::{
+    EnhancedPaging, PagerAddress, PagingContent, PagingDigitString, PagingMessage, PagingNetwork,
+    PagingTextString, MAX_PAGING_DIGITS, MAX_PAGING_SEGMENTS, MAX_PAGING_TEXT_LENGTH,
+};
+
+#[cfg(feature = "unstable")]
+mod rds2;
+#[cfg(feature = "unstable")]
+pub use rds2::{
+    FunctionHeader, TypeCGroup, RDS2_STREAMS, RFT_AID, TYPE_C_CHANNELS, TYPE_C_DATA_SIZE,
+};
+
+mod tmc;
+pub use tmc::{
+    TmcDirection, TmcDurationType, TmcEncryption, TmcEncryptionKey, TmcEventInfo, TmcEventNature,
+    TmcFrequencyList, TmcKeyTable, TmcKeyTableSelector, TmcLabel, TmcLabelList, TmcMessage,
+    TmcOtherNetwork, TmcOtherNetworkList, TmcProviderNameString, TmcQuantifierType, TmcQuantity,
+    TmcScope, TmcService, MAX_TMC_FREQUENCIES, MAX_TMC_LABELS, MAX_TMC_OTHER_NETWORKS,
+    TMC_KEY_TABLE_SIZE, TMC_PROVIDER_NAME_LENGTH,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
@@ -20,6 +57,10 @@ pub struct Block4(pub u16);
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
+    /// Data stream of the group: 0 for RDS, 1 to 3 for the additional RDS2 streams.
+    ///
+    /// Block 1 carries the PI only on stream 0.
+    pub stream: u8,
     pub block1: Option<Block1>,
     pub block2: Option<Block2>,
     pub block3: Option<Block3>,
@@ -35,8 +76,20 @@ impl Message {
         block2: Option<u16>,
         block3: Option<u16>,
         block4: Option<u16>,
+    ) -> Self {
+        Self::with_stream(0, block1, block2, block3, block4)
+    }
+
+    /// Create RDS Blocks struct for a group received on an RDS2 data stream.
+    pub fn with_stream(
+        stream: u8,
+        block1: Option<u16>,
+        block2: Option<u16>,
+        block3: Option<u16>,
+        block4: Option<u16>,
     ) -> Self {
         Self {
+            stream,
             block1: block1.map(Block1),
             block2: block2.map(Block2),
             block3: block3.map(Block3),
@@ -51,6 +104,12 @@ pub struct ProgrammeIdentifier(pub u16);
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TrafficProgram(pub bool);
 
+/// Traffic Announcement (TA) flag from groups 0A, 0B and 15B.
+///
+/// Together with TP set, it indicates that a traffic announcement is on air.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrafficAnnouncement(pub bool);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ProgrammeType(pub u8);
 
@@ -108,6 +167,20 @@ impl TryFrom<u8> for GroupType {
     }
 }
 
+/// Code table selected by the switching sequences of PS, RT and PTYN.
+///
+/// Every table shares the lower half of the basic G0 table. Only the G0 table is
+/// transcribed from IEC 62106 / EN 50067 Annex E: the characters of the upper halves
+/// of G1, G2 and G3 are decoded as spaces, but the active table is still reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RdsCodeTable {
+    #[default]
+    G0,
+    G1,
+    G2,
+    G3,
+}
+
 pub const PS_LENGTH: usize = 8;
 
 pub const PS_BYTE_SIZE: usize = PS_LENGTH * size_of::<char>();
@@ -117,16 +190,68 @@ pub type ProgrammeServiceNameString = heapless::String<PS_BYTE_SIZE>;
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ProgrammeServiceName {
     ps: ProgrammeServiceNameString,
+    code_table: RdsCodeTable,
 }
 
 impl ProgrammeServiceName {
     pub fn new(ps: ProgrammeServiceNameString) -> Self {
-        Self { ps }
+        Self {
+            ps,
+            code_table: RdsCodeTable::G0,
+        }
+    }
+
+    /// Sets the code table active at the end of the PS
+    pub fn with_code_table(mut self, code_table: RdsCodeTable) -> Self {
+        self.code_table = code_table;
+        self
     }
 
     pub fn as_str(&self) -> &str {
         &self.ps
     }
+
+    /// Returns the code table active at the end of the PS
+    pub fn code_table(&self) -> RdsCodeTable {
+        self.code_table
+    }
+}
+
+pub const PTYN_LENGTH: usize = 8;
+
+pub const PTYN_BYTE_SIZE: usize = PTYN_LENGTH * size_of::<char>();
+
+pub type ProgrammeTypeNameString = heapless::String<PTYN_BYTE_SIZE>;
+
+/// Programme Type Name (PTYN) from group 10A, describing the PTY more specifically
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgrammeTypeName {
+    ptyn: ProgrammeTypeNameString,
+    code_table: RdsCodeTable,
+}
+
+impl ProgrammeTypeName {
+    pub fn new(ptyn: ProgrammeTypeNameString) -> Self {
+        Self {
+            ptyn,
+            code_table: RdsCodeTable::G0,
+        }
+    }
+
+    /// Sets the code table active at the end of the PTYN
+    pub fn with_code_table(mut self, code_table: RdsCodeTable) -> Self {
+        self.code_table = code_table;
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.ptyn
+    }
+
+    /// Returns the code table active at the end of the PTYN
+    pub fn code_table(&self) -> RdsCodeTable {
+        self.code_table
+    }
 }
 
 /// Max size of Group A RadioText messages
@@ -144,11 +269,27 @@ pub type RadioTextPlusList = heapless::Vec<RadioTextPlusTag, MAX_RT_PLUS_TAGS>;
 pub struct RadioText {
     rt: RadioTextString,
     rt_plus: RadioTextPlusList,
+    code_table: RdsCodeTable,
 }
 
 impl RadioText {
     pub fn new(rt: RadioTextString, rt_plus: RadioTextPlusList) -> Self {
-        Self { rt, rt_plus }
+        Self {
+            rt,
+            rt_plus,
+            code_table: RdsCodeTable::G0,
+        }
+    }
+
+    /// Sets the code table active at the end of the RadioText
+    pub fn with_code_table(mut self, code_table: RdsCodeTable) -> Self {
+        self.code_table = code_table;
+        self
+    }
+
+    /// Returns the code table active at the end of the RadioText
+    pub fn code_table(&self) -> RdsCodeTable {
+        self.code_table
     }
 
     pub fn as_str(&self) -> &str {
@@ -329,6 +470,121 @@ impl TryFrom<u8> for RadioTextPlusContentType {
     }
 }
 
+/// Maximum number of ODA announcements kept in [`Metadata`]
+pub const MAX_ODA_ANNOUNCEMENTS: usize = 8;
+
+pub type OdaAnnouncementList = heapless::Vec<OdaAnnouncement, MAX_ODA_ANNOUNCEMENTS>;
+
+/// Open Data Application (ODA) announced by the station in group 3A.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OdaAnnouncement {
+    aid: u16,
+    group_type: GroupType,
+    group_variant: GroupVariant,
+    message: u16,
+}
+
+impl OdaAnnouncement {
+    pub fn new(aid: u16, group_type: GroupType, group_variant: GroupVariant, message: u16) -> Self {
+        Self {
+            aid,
+            group_type,
+            group_variant,
+            message,
+        }
+    }
+
+    /// Application Identifier (AID)
+    pub fn aid(&self) -> u16 {
+        self.aid
+    }
+
+    /// Group type carrying the application.
+    ///
+    /// Group type 0A means the application does not use a dedicated group.
+    pub fn group_type(&self) -> GroupType {
+        self.group_type
+    }
+
+    pub fn group_variant(&self) -> GroupVariant {
+        self.group_variant
+    }
+
+    /// Most recent 16-bit message bits from Block 3 of group 3A
+    pub fn message(&self) -> u16 {
+        self.message
+    }
+
+    /// Returns the registered name and specification of the application
+    pub fn info(&self) -> Option<&'static OdaInfo> {
+        lookup_aid(self.aid)
+    }
+}
+
+/// Emergency Warning System (EWS) message from group 9A.
+///
+/// The format of the message is defined nationally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EmergencyWarning {
+    /// EWS channel identification from group 1A variant 7, if received
+    pub channel_id: Option<u16>,
+    /// The 37 message bits: 5 bits of Block 2 followed by Blocks 3 and 4
+    pub message: u64,
+}
+
+impl EmergencyWarning {
+    /// Returns the 5 message bits at the end of Block 2
+    pub fn block2_data(&self) -> u8 {
+        (self.message >> 32) as u8
+    }
+
+    /// Returns the message bits of Block 3
+    pub fn block3(&self) -> u16 {
+        (self.message >> 16) as u16
+    }
+
+    /// Returns the message bits of Block 4
+    pub fn block4(&self) -> u16 {
+        self.message as u16
+    }
+}
+
+/// Payload of an in-house group 6A or 6B.
+///
+/// The content is defined by the broadcaster, for example for transmitter control.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InHouseData {
+    /// PI of the group, or the filtered PI if Block 1 was not received
+    pub pi: Option<ProgrammeIdentifier>,
+    pub variant: GroupVariant,
+    /// The 5 data bits at the end of Block 2
+    pub block2_data: u8,
+    /// Block 3, which only carries data in group 6A
+    pub block3: Option<u16>,
+    pub block4: u16,
+}
+
+/// Event produced by the decoder.
+///
+/// Unlike [`Metadata`], events are reported once and are retrieved through
+/// [`crate::Decoder::poll_event`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Traffic Message Channel (TMC) user message
+    TmcMessage(TmcMessage),
+    /// Emergency Warning System (EWS) message
+    EmergencyWarning(EmergencyWarning),
+    /// The station started signalling an alarm (PTY 31)
+    AlarmStarted,
+    /// The station stopped signalling an alarm (PTY 31)
+    AlarmEnded,
+    /// Radio paging message, only decoded with the `unstable` feature
+    #[cfg(feature = "unstable")]
+    Paging(PagingMessage),
+    /// In-house data from group 6, queued only while no in-house callback is set
+    InHouse(InHouseData),
+}
+
 /// This represents the current state of the RDS metadata that has come in so far.
 /// Only the completed metadata is stored within this struct (e.g., incomplete PS segments).
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -336,8 +592,36 @@ pub struct Metadata {
     pub pi: Option<ProgrammeIdentifier>,
     pub pty: Option<ProgrammeType>,
     pub tp: Option<TrafficProgram>,
+    pub ta: Option<TrafficAnnouncement>,
+    /// Alarm (PTY 31) in progress, detected with less filtering than `pty`
+    pub alarm: bool,
     pub ps: Option<ProgrammeServiceName>,
     pub rt: Option<RadioText>,
+    pub oda: OdaAnnouncementList,
+    pub tmc: Option<TmcService>,
+    /// Extended Country Code (ECC) from group 1A
+    pub ecc: Option<u8>,
+    pub paging: Option<PagingInfo>,
+    /// Linkage Actuator (LA) from group 1A: the programme is currently linked
+    pub linkage_actuator: bool,
+    /// Linkage information of the tuned and other networks from group 14A
+    pub linkage: NetworkLinkageList,
+    /// DAB ensembles carrying the programme, from the DAB cross-referencing ODA
+    pub dab: DabLinkageList,
+    /// Programme Type Name (PTYN) from group 10A
+    pub ptyn: Option<ProgrammeTypeName>,
+}
+
+impl Metadata {
+    /// Returns true if the receiver should switch to the station for an announcement:
+    /// during an alarm, or a traffic announcement (TP and TA both set).
+    pub fn is_announcement(&self) -> bool {
+        let traffic = matches!(
+            (self.tp, self.ta),
+            (Some(TrafficProgram(true)), Some(TrafficAnnouncement(true)))
+        );
+        self.alarm || traffic
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,55 @@
+//! DAB cross-referencing (RDS-DAB) types, following ETSI EN 301 700.
+
+/// Maximum number of DAB ensembles kept in [`crate::Metadata`]
+pub const MAX_DAB_LINKAGES: usize = 8;
+
+/// Maximum number of DAB services kept for each ensemble
+pub const MAX_DAB_SERVICES: usize = 4;
+
+pub type DabLinkageList = heapless::Vec<DabLinkage, MAX_DAB_LINKAGES>;
+
+pub type DabServiceIdList = heapless::Vec<u16, MAX_DAB_SERVICES>;
+
+/// DAB transmission mode of an ensemble
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DabMode {
+    #[default]
+    Unspecified,
+    ModeI,
+    ModeIIOrIII,
+    ModeIV,
+}
+
+impl From<u8> for DabMode {
+    fn from(value: u8) -> Self {
+        match value & 0b11 {
+            1 => DabMode::ModeI,
+            2 => DabMode::ModeIIOrIII,
+            3 => DabMode::ModeIV,
+            _ => DabMode::Unspecified,
+        }
+    }
+}
+
+/// DAB ensemble carrying the programme of the FM station
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DabLinkage {
+    /// Ensemble identifier (EId)
+    pub ensemble_id: u16,
+    /// Centre frequency of the ensemble in kHz, once the ensemble table is received
+    pub frequency: Option<u32>,
+    pub mode: DabMode,
+    /// Service identifiers (SId) of the ensemble linked to the FM station
+    pub service_ids: DabServiceIdList,
+}
+
+impl DabLinkage {
+    pub fn new(ensemble_id: u16) -> Self {
+        Self {
+            ensemble_id,
+            frequency: None,
+            mode: DabMode::Unspecified,
+            service_ids: DabServiceIdList::new(),
+        }
+    }
+}
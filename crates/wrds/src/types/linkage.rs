@@ -0,0 +1,56 @@
+//! Programme linkage types from groups 1A and 14A.
+
+use super::ProgrammeIdentifier;
+
+/// Maximum number of networks with linkage information kept in [`crate::Metadata`]
+pub const MAX_LINKED_NETWORKS: usize = 16;
+
+pub type NetworkLinkageList = heapless::Vec<NetworkLinkage, MAX_LINKED_NETWORKS>;
+
+pub type LinkedProgrammeList = heapless::Vec<ProgrammeIdentifier, MAX_LINKED_NETWORKS>;
+
+/// Linkage information from group 14A variant 12
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LinkageInfo {
+    /// Linkage Actuator (LA): the network currently carries the linked programme
+    pub linkage_actuator: bool,
+    /// Extended Generic (EG): the networks carry related rather than identical programmes
+    pub extended_generic: bool,
+    /// International Linkage Set (ILS): the set may span several countries
+    pub international: bool,
+    /// Linkage Set Number (LSN, 12 bits), 0 if the network is not linked
+    pub linkage_set_number: u16,
+}
+
+impl LinkageInfo {
+    /// Returns true if both networks belong to the same linkage set
+    pub fn same_set(&self, other: &LinkageInfo) -> bool {
+        self.linkage_set_number != 0
+            && self.linkage_set_number == other.linkage_set_number
+            && self.extended_generic == other.extended_generic
+            && self.international == other.international
+    }
+}
+
+impl From<u16> for LinkageInfo {
+    fn from(value: u16) -> Self {
+        const LA_BITMASK: u16 = 0x8000;
+        const EG_BITMASK: u16 = 0x4000;
+        const ILS_BITMASK: u16 = 0x2000;
+        const LSN_BITMASK: u16 = 0xFFF;
+
+        Self {
+            linkage_actuator: value & LA_BITMASK != 0,
+            extended_generic: value & EG_BITMASK != 0,
+            international: value & ILS_BITMASK != 0,
+            linkage_set_number: value & LSN_BITMASK,
+        }
+    }
+}
+
+/// Linkage information of a network, which may be the tuned network itself
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetworkLinkage {
+    pub pi: ProgrammeIdentifier,
+    pub info: LinkageInfo,
+}
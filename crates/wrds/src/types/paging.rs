@@ -0,0 +1,96 @@
+//! Radio Paging (RP) types.
+
+/// Maximum number of groups following the first group of a paging message
+#[cfg(feature = "unstable")]
+pub const MAX_PAGING_SEGMENTS: usize = 15;
+
+/// Maximum number of digits of a numeric paging message
+#[cfg(feature = "unstable")]
+pub const MAX_PAGING_DIGITS: usize = MAX_PAGING_SEGMENTS * 8;
+
+/// Maximum number of characters of an alphanumeric paging message
+#[cfg(feature = "unstable")]
+pub const MAX_PAGING_TEXT_LENGTH: usize = MAX_PAGING_SEGMENTS * 4;
+
+#[cfg(feature = "unstable")]
+pub type PagingDigitString = heapless::String<MAX_PAGING_DIGITS>;
+
+#[cfg(feature = "unstable")]
+pub type PagingTextString = heapless::String<{ MAX_PAGING_TEXT_LENGTH * size_of::<char>() }>;
+
+/// Address of a pager: a 2-digit group code followed by a 4-digit individual code
+#[cfg(feature = "unstable")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PagerAddress {
+    /// Group code (Y1 Y2), 0 to 99
+    pub group: u8,
+    /// Individual code (Z1 Z2 Z3 Z4), 0 to 9999
+    pub individual: u16,
+}
+
+/// Content of a paging message
+#[cfg(feature = "unstable")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PagingContent {
+    /// Tone-only page without message
+    Beep,
+    Numeric(PagingDigitString),
+    Alphanumeric(PagingTextString),
+}
+
+/// Paging network of a station, which together with the [`PagerAddress`] forms the
+/// international address of a pager
+#[cfg(feature = "unstable")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PagingNetwork {
+    /// Extended Country Code (ECC)
+    pub ecc: u8,
+    /// Country code from the first 4 bits of the PI
+    pub country_code: u8,
+    /// Paging operator code (OPC)
+    pub operator_code: u8,
+    /// Paging area code (PAC)
+    pub area_code: u8,
+}
+
+/// Paging message from group 7A
+///
+/// Experimental: the group 7A layout is not verified against the standard.
+#[cfg(feature = "unstable")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PagingMessage {
+    pub pager: PagerAddress,
+    pub content: PagingContent,
+    /// Network of the station, known once it identifies its enhanced paging service
+    pub network: Option<PagingNetwork>,
+}
+
+/// Paging parameters of the station from group 1A
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PagingInfo {
+    /// Transmitter network group designation (3 bits)
+    pub network_group: u8,
+    /// Battery saving interval synchronisation and identification (2 bits)
+    pub battery_saving_interval: u8,
+    /// Paging operator code (OPC) from slow labelling variant 0 or 2
+    pub operator_code: Option<u8>,
+    /// Paging area code (PAC) from slow labelling variant 2
+    pub area_code: Option<u8>,
+    /// Enhanced paging system information, only decoded with the `unstable` feature
+    #[cfg(feature = "unstable")]
+    pub enhanced: Option<EnhancedPaging>,
+}
+
+/// Enhanced paging system information from group 13A
+///
+/// Experimental: the group 13A layout is not verified against the standard.
+#[cfg(feature = "unstable")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EnhancedPaging {
+    /// Cycle selection (2 bits)
+    pub cycle: u8,
+    /// Number of the current interval within the cycle (4 bits)
+    pub interval: u8,
+    /// Number of the batch of pagers addressed in the interval (4 bits)
+    pub batch: u8,
+}
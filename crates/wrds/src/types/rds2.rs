@@ -0,0 +1,91 @@
+//! RDS2 types for the additional data streams.
+//!
+//! Groups on the streams 1 to 3 are type C groups: the first byte of Block 1 is a
+//! function header instead of the PI, followed by 7 data bytes. The two most
+//! significant bits `00` of the function header select an ODA channel (6 bits) and
+//! the bits `1000` select an RFT pipe (4 bits). Other function headers are reserved.
+//!
+//! ODA channel 0 assigns the other channels: its first two data bytes carry the AID
+//! and its third data byte the assigned channel.
+//!
+//! The function header and channel assignment layouts have not been checked against
+//! the type C group clauses of IEC 62106-2 nor against recordings of RDS2
+//! broadcasts, which is why they are only built with the `unstable` feature.
+
+/// Number of RDS2 data streams, including the RDS stream 0
+pub const RDS2_STREAMS: u8 = 4;
+
+/// RDS2 file transfer (RFT) application identifier (AID), whose handlers receive
+/// the type C groups of the RFT pipes
+pub const RFT_AID: u16 = 0xFF7F;
+
+/// Number of ODA channels of type C groups, including the assignment channel 0
+pub const TYPE_C_CHANNELS: usize = 64;
+
+/// Number of data bytes of a type C group following the function header
+pub const TYPE_C_DATA_SIZE: usize = 7;
+
+const CHANNEL_BITMASK: u8 = 0x3F;
+const PIPE_BITMASK: u8 = 0x0F;
+
+/// Function header in the first byte of a type C group
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FunctionHeader {
+    /// ODA data on a channel assigned to an AID
+    Oda {
+        channel: u8,
+    },
+    /// RDS2 file transfer (RFT) data on a pipe
+    Rft {
+        pipe: u8,
+    },
+    Reserved(u8),
+}
+
+impl From<u8> for FunctionHeader {
+    fn from(value: u8) -> Self {
+        match value >> 4 {
+            0b0000..=0b0011 => FunctionHeader::Oda {
+                channel: value & CHANNEL_BITMASK,
+            },
+            0b1000 => FunctionHeader::Rft {
+                pipe: value & PIPE_BITMASK,
+            },
+            _ => FunctionHeader::Reserved(value),
+        }
+    }
+}
+
+/// Type C group received on one of the RDS2 streams 1 to 3
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TypeCGroup {
+    pub stream: u8,
+    pub function_header: FunctionHeader,
+    pub data: [u8; TYPE_C_DATA_SIZE],
+}
+
+impl TypeCGroup {
+    /// Parse the 4 blocks of a type C group
+    pub fn new(stream: u8, blocks: [u16; 4]) -> Self {
+        let [header, byte0] = blocks[0].to_be_bytes();
+        let [byte1, byte2] = blocks[1].to_be_bytes();
+        let [byte3, byte4] = blocks[2].to_be_bytes();
+        let [byte5, byte6] = blocks[3].to_be_bytes();
+        Self {
+            stream,
+            function_header: FunctionHeader::from(header),
+            data: [byte0, byte1, byte2, byte3, byte4, byte5, byte6],
+        }
+    }
+
+    /// Returns the AID and channel if the group is a channel assignment on channel 0.
+    pub fn channel_assignment(&self) -> Option<(u16, u8)> {
+        match self.function_header {
+            FunctionHeader::Oda { channel: 0 } => Some((
+                u16::from_be_bytes([self.data[0], self.data[1]]),
+                self.data[2] & CHANNEL_BITMASK,
+            )),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! Traffic Message Channel (RDS-TMC) types, following ALERT-C (ISO 14819-1).
+
+use crate::decoder::tmc_decoder::events::{interpret_quantifier, lookup_event, route_length_km};
+
+/// Direction of queue growth relative to the location table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcDirection {
+    Positive,
+    Negative,
+}
+
+impl From<bool> for TmcDirection {
+    fn from(value: bool) -> Self {
+        match value {
+            true => TmcDirection::Negative,
+            false => TmcDirection::Positive,
+        }
+    }
+}
+
+/// TMC user message
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TmcMessage {
+    /// ALERT-C event code (11 bits)
+    pub event: u16,
+    /// Primary location code from the location table
+    pub location: u16,
+    pub direction: TmcDirection,
+    /// Number of steps from the primary location in the direction of queue growth
+    pub extent: u8,
+    /// Duration and persistence code (3 bits)
+    pub duration: u8,
+    /// Diversion advice
+    pub diversion: bool,
+    /// Optional content of multi-group messages
+    pub labels: TmcLabelList,
+}
+
+/// Maximum number of optional content labels in a TMC message
+pub const MAX_TMC_LABELS: usize = 16;
+
+pub type TmcLabelList = heapless::Vec<TmcLabel, MAX_TMC_LABELS>;
+
+/// Optional content of a TMC multi-group message
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcLabel {
+    /// Duration and persistence code (label 0)
+    Duration(u8),
+    /// Control code (label 1)
+    ControlCode(u8),
+    /// Length of route affected (label 2)
+    RouteLength(u8),
+    /// Speed limit advice (label 3)
+    SpeedLimit(u8),
+    /// 5-bit quantifier (label 4)
+    Quantifier5(u8),
+    /// 8-bit quantifier (label 5)
+    Quantifier8(u8),
+    /// Supplementary information code (label 6)
+    SupplementaryInformation(u8),
+    /// Explicit start time (label 7)
+    StartTime(u8),
+    /// Explicit stop time (label 8)
+    StopTime(u8),
+    /// Additional event code (label 9)
+    AdditionalEvent(u16),
+    /// Detailed diversion instructions as a location code (label 10)
+    DiversionRoute(u16),
+    /// Destination as a location code (label 11)
+    Destination(u16),
+    /// Cross linkage to the source of the problem on another route (label 13)
+    CrossLinkage(u16),
+    /// Separator between independent parts of a message (label 14)
+    Separator,
+    /// Label reserved for future use (labels 12 and 15)
+    Reserved { label: u8, value: u16 },
+}
+
+/// Length of the TMC service provider name
+pub const TMC_PROVIDER_NAME_LENGTH: usize = 8;
+
+pub type TmcProviderNameString = heapless::String<{ TMC_PROVIDER_NAME_LENGTH * size_of::<char>() }>;
+
+/// Maximum number of other networks tracked for a TMC service
+pub const MAX_TMC_OTHER_NETWORKS: usize = 8;
+
+/// Maximum number of frequencies tracked for each other network
+pub const MAX_TMC_FREQUENCIES: usize = 8;
+
+pub type TmcFrequencyList = heapless::Vec<u32, MAX_TMC_FREQUENCIES>;
+
+pub type TmcOtherNetworkList = heapless::Vec<TmcOtherNetwork, MAX_TMC_OTHER_NETWORKS>;
+
+/// Geographic scope of a TMC service (message geographical scope)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TmcScope {
+    pub international: bool,
+    pub national: bool,
+    pub regional: bool,
+    pub urban: bool,
+}
+
+/// Other network (ON) carrying the same TMC service, from the tuning information groups
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TmcOtherNetwork {
+    /// Programme Identifier (PI) of the other network
+    pub pi: u16,
+    /// Frequencies of the other network in kHz
+    pub frequencies: TmcFrequencyList,
+    /// Location table number, if the other network uses different system parameters
+    pub location_table: Option<u8>,
+    /// Service identifier, if the other network uses different system parameters
+    pub service_id: Option<u8>,
+    /// Geographic scope, if the other network uses different system parameters
+    pub scope: Option<TmcScope>,
+}
+
+impl TmcOtherNetwork {
+    pub fn new(pi: u16) -> Self {
+        Self {
+            pi,
+            frequencies: TmcFrequencyList::new(),
+            location_table: None,
+            service_id: None,
+            scope: None,
+        }
+    }
+}
+
+/// Description of the TMC service carried by the station.
+///
+/// System information is announced in group 3A, tuning information
+/// in the TMC groups with the tuning flag set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TmcService {
+    /// Location table number (LTN). Zero indicates an encrypted service.
+    pub location_table: u8,
+    /// Alternative frequency indicator (AFI)
+    pub alternative_frequencies: bool,
+    /// Enhanced mode with time windows, basic mode otherwise
+    pub enhanced_mode: bool,
+    pub scope: TmcScope,
+    /// Service identifier (SID)
+    pub service_id: Option<u8>,
+    /// Number of groups between TMC groups in the gap parameter, from group 3A variant 1
+    pub gap: Option<u8>,
+    /// Name of the service provider
+    pub provider_name: Option<TmcProviderNameString>,
+    pub other_networks: TmcOtherNetworkList,
+    /// Encryption parameters, if the service encrypts its location codes
+    pub encryption: Option<TmcEncryption>,
+}
+
+/// Number of keys in a TMC key table, one for each encryption ID
+pub const TMC_KEY_TABLE_SIZE: usize = 32;
+
+/// Key table used by an encrypted TMC service
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcKeyTableSelector {
+    /// Key table of the regular service
+    Service,
+    /// Key table used while the service is in test mode
+    Test,
+}
+
+/// Encryption parameters from the TMC encryption administration group
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TmcEncryption {
+    /// Encryption ID (ENCID), the index of the key in the key table
+    pub encryption_id: u8,
+    pub key_table: TmcKeyTableSelector,
+    /// Service identifier (SID)
+    pub service_id: u8,
+    /// Location table number before encryption (LTNBE)
+    pub location_table: u8,
+}
+
+/// Key used to encrypt the location codes of a TMC service.
+///
+/// Location codes are encrypted by rotating them right by `rotation` bits and
+/// XORing the result with `xor_value` shifted left by `xor_start` bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TmcEncryptionKey {
+    pub xor_value: u8,
+    /// Shift of the XOR value (0 to 15)
+    pub xor_start: u8,
+    /// Number of bits of the rotation (0 to 15)
+    pub rotation: u8,
+}
+
+impl TmcEncryptionKey {
+    pub const fn new(xor_value: u8, xor_start: u8, rotation: u8) -> Self {
+        Self {
+            xor_value,
+            xor_start,
+            rotation,
+        }
+    }
+
+    /// Encrypt a location code
+    pub fn encrypt(&self, location: u16) -> u16 {
+        location.rotate_right(self.rotation()) ^ self.mask()
+    }
+
+    /// Decrypt an encrypted location code
+    pub fn decrypt(&self, location: u16) -> u16 {
+        (location ^ self.mask()).rotate_left(self.rotation())
+    }
+
+    fn mask(&self) -> u16 {
+        u16::from(self.xor_value) << (self.xor_start & 0xF)
+    }
+
+    fn rotation(&self) -> u32 {
+        u32::from(self.rotation & 0xF)
+    }
+}
+
+/// Keys of a TMC key table, indexed by encryption ID
+pub type TmcKeyTable = [TmcEncryptionKey; TMC_KEY_TABLE_SIZE];
+
+/// Nature of an ALERT-C event
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcEventNature {
+    Information,
+    Forecast,
+    /// Silent events are not presented to the user
+    Silent,
+}
+
+/// Duration type of an ALERT-C event, selecting how the duration code is interpreted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcDurationType {
+    Dynamic,
+    LongerLasting,
+}
+
+/// Quantifier type of an ALERT-C event
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcQuantifierType {
+    /// Small numbers (type 0)
+    SmallNumber,
+    /// Numbers (type 1)
+    Number,
+    /// Less than a distance in metres (type 2)
+    LessThanMetres,
+    /// Percentage (type 3)
+    Percentage,
+    /// Speed in km/h (type 4)
+    Speed,
+    /// Length in metres or km (type 5)
+    Length,
+    /// Temperature in °C (type 6)
+    Temperature,
+    /// Time of day (type 7)
+    Time,
+    /// Weight in tonnes (type 8)
+    Weight,
+    /// Dimension in metres (type 9)
+    Dimension,
+    /// Precipitation in mm (type 10)
+    Precipitation,
+    /// FM frequency (type 11)
+    FrequencyMhz,
+    /// LF/MF frequency (type 12)
+    FrequencyKhz,
+}
+
+impl TmcQuantifierType {
+    /// Returns true for the types 0 to 5 carried by the 5-bit quantifier (label 4),
+    /// false for the types carried by the 8-bit quantifier (label 5)
+    pub fn is_five_bit(&self) -> bool {
+        matches!(
+            self,
+            Self::SmallNumber
+                | Self::Number
+                | Self::LessThanMetres
+                | Self::Percentage
+                | Self::Speed
+                | Self::Length
+        )
+    }
+}
+
+/// Interpreted value of a TMC quantifier
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TmcQuantity {
+    Count(u16),
+    Metres(u16),
+    Percent(u16),
+    SpeedKmh(u16),
+    LengthMetres(u16),
+    Celsius(i16),
+    Time { hours: u8, minutes: u8 },
+    TenthsOfTonnes(u16),
+    TenthsOfMetres(u16),
+    PrecipitationMm(u8),
+    FrequencyKhz(u32),
+}
+
+/// Entry of the ALERT-C event list
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TmcEventInfo {
+    pub code: u16,
+    /// Description of the event. `Q` marks where the quantity is inserted.
+    pub text: &'static str,
+    pub nature: TmcEventNature,
+    /// Update class used to replace older messages (1 to 39)
+    pub update_class: u8,
+    pub duration_type: TmcDurationType,
+    pub quantifier: Option<TmcQuantifierType>,
+}
+
+impl TmcMessage {
+    /// Returns the event list entry of the message's event code
+    pub fn event_info(&self) -> Option<&'static TmcEventInfo> {
+        lookup_event(self.event)
+    }
+
+    /// Returns whether the message is dynamic or longer lasting.
+    ///
+    /// This is the duration type of the event, interchanged by control code 3
+    /// (label 1). Events missing from the event list are dynamic.
+    pub fn duration_type(&self) -> TmcDurationType {
+        /// Control code interchanging the dynamic and longer lasting duration types
+        const CONTROL_DURATION_TYPE: u8 = 3;
+
+        let duration_type = self
+            .event_info()
+            .map_or(TmcDurationType::Dynamic, |info| info.duration_type);
+        if !self
+            .labels
+            .contains(&TmcLabel::ControlCode(CONTROL_DURATION_TYPE))
+        {
+            return duration_type;
+        }
+        match duration_type {
+            TmcDurationType::Dynamic => TmcDurationType::LongerLasting,
+            TmcDurationType::LongerLasting => TmcDurationType::Dynamic,
+        }
+    }
+
+    /// Returns the quantity of the message, interpreted using the event's quantifier type.
+    ///
+    /// Only the quantifier label matching the width of the type is used.
+    pub fn quantity(&self) -> Option<TmcQuantity> {
+        let quantifier = self.event_info()?.quantifier?;
+        self.labels.iter().find_map(|label| match *label {
+            TmcLabel::Quantifier5(value) if quantifier.is_five_bit() => {
+                Some(interpret_quantifier(quantifier, value))
+            }
+            TmcLabel::Quantifier8(value) if !quantifier.is_five_bit() => {
+                Some(interpret_quantifier(quantifier, value))
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns the length of the affected route in km (label 2).
+    ///
+    /// Returns `Some(None)` if the route is longer than 100 km.
+    pub fn route_length_km(&self) -> Option<Option<u8>> {
+        self.labels.iter().find_map(|label| match *label {
+            TmcLabel::RouteLength(value) => Some(route_length_km(value)),
+            _ => None,
+        })
+    }
+
+    /// Returns the speed limit advice in km/h (label 3)
+    pub fn speed_limit_kmh(&self) -> Option<u16> {
+        self.labels.iter().find_map(|label| match *label {
+            TmcLabel::SpeedLimit(value) => Some(u16::from(value) * 5),
+            _ => None,
+        })
+    }
+}
@@ -1,4 +1,14 @@
-use wrds::{Decoder, Message, Metadata, ProgrammeIdentifier, ProgrammeType, TrafficProgram};
+use wrds::{
+    DabLinkage, DabMode, Decoder, EmergencyWarning, Event, GroupType, GroupVariant, InHouseData,
+    LinkageInfo, Message, Metadata, NetworkLinkage, OdaAnnouncement, OdaGroup, OdaHandler,
+    ProgrammeIdentifier, ProgrammeType, RdsCharsetEncoder, RdsCodeTable, TmcDirection, TmcMessage,
+    TrafficAnnouncement, TrafficProgram, MAX_TEXT_VOTES,
+};
+#[cfg(feature = "unstable")]
+use wrds::{
+    EnhancedPaging, FunctionHeader, PagerAddress, PagingContent, PagingMessage, PagingNetwork,
+    TypeCGroup,
+};
 
 /// Verifies that:
 ///   - Decoder will do nothing if empty RDS message is decoded.
@@ -57,3 +67,878 @@ fn block3_pi() {
         }
     )
 }
+
+/// Verifies that:
+///   - A registered ODA handler receives the group 3A message bits for its AID.
+///   - Groups of the announced type and variant are dispatched to the handler.
+#[test]
+fn oda_handler_dispatch() {
+    #[derive(Default)]
+    struct Handler {
+        message: Option<u16>,
+        groups: Vec<(u8, Option<u16>, Option<u16>)>,
+    }
+
+    impl OdaHandler for Handler {
+        fn aid(&self) -> u16 {
+            0x1234
+        }
+
+        fn identify(&mut self, group_type: GroupType, group_variant: GroupVariant, message: u16) {
+            assert_eq!(group_type, GroupType(11));
+            assert_eq!(group_variant, GroupVariant::A);
+            self.message = Some(message);
+        }
+
+        fn decode_group(&mut self, group: &OdaGroup) {
+            self.groups.push((
+                group.block2_data(),
+                group.block3.map(|block| block.0),
+                group.block4.map(|block| block.0),
+            ));
+        }
+    }
+
+    let mut handler = Handler::default();
+    {
+        let mut decoder = Decoder::default();
+        decoder.register_oda_handler(&mut handler).unwrap();
+
+        // Group 11A is dispatched only after the AID has been announced
+        decoder.decode(&Message::new(
+            None,
+            Some(0xB005),
+            Some(0x1111),
+            Some(0x2222),
+        ));
+        // Group 3A: 11A carries AID 0x1234 with message bits 0xBEEF
+        decoder.decode(&Message::new(
+            None,
+            Some(0x3016),
+            Some(0xBEEF),
+            Some(0x1234),
+        ));
+        decoder.decode(&Message::new(
+            None,
+            Some(0xB005),
+            Some(0x1111),
+            Some(0x2222),
+        ));
+        // Group 11B is not assigned to the application
+        decoder.decode(&Message::new(
+            None,
+            Some(0xB805),
+            Some(0x1111),
+            Some(0x2222),
+        ));
+    }
+
+    assert_eq!(handler.message, Some(0xBEEF));
+    assert_eq!(handler.groups, vec![(0x05, Some(0x1111), Some(0x2222))]);
+}
+
+/// Verifies that:
+///   - Every AID announced in group 3A is exposed in the metadata.
+///   - Groups carrying an ODA without a handler are passed to the unknown ODA callback.
+///   - Groups of the RT+ and TMC applications decoded by the crate are not.
+#[test]
+fn unknown_oda_announcement() {
+    let mut unknown = Vec::new();
+    let mut callback = |aid: u16, group: &OdaGroup| {
+        unknown.push((aid, group.block3.map(|block| block.0)));
+    };
+    let metadata = {
+        let mut decoder = Decoder::default();
+        decoder.set_unknown_oda_callback(&mut callback);
+
+        // Group 3A: 12A carries AID 0xABCD with message bits 0x0042
+        decoder.decode(&Message::new(
+            None,
+            Some(0x3018),
+            Some(0x0042),
+            Some(0xABCD),
+        ));
+        // Group 3A: 11A carries RT+, 8A carries TMC
+        decoder.decode(&Message::new(
+            None,
+            Some(0x3016),
+            Some(0x0000),
+            Some(0x4BD7),
+        ));
+        decoder.decode(&Message::new(
+            None,
+            Some(0x3010),
+            Some(0x0000),
+            Some(0xCD46),
+        ));
+        decoder.decode(&Message::new(
+            None,
+            Some(0xB000),
+            Some(0x0000),
+            Some(0x0000),
+        ));
+        decoder.decode(&Message::new(
+            None,
+            Some(0x8000),
+            Some(0x0000),
+            Some(0x0000),
+        ));
+        decoder.decode(&Message::new(
+            None,
+            Some(0xC000),
+            Some(0x1111),
+            Some(0x2222),
+        ))
+    };
+
+    assert_eq!(metadata.oda.len(), 3);
+    assert_eq!(
+        metadata.oda[0],
+        OdaAnnouncement::new(0xABCD, GroupType(12), GroupVariant::A, 0x0042)
+    );
+    assert_eq!(unknown, vec![(0xABCD, Some(0x1111))]);
+}
+
+/// Verifies that:
+///   - ODA registrations are cleared when the decoder is reset.
+///   - Groups of a previous station's ODA are no longer dispatched after reset.
+#[test]
+fn reset_clears_oda() {
+    let mut unknown = 0;
+    let mut callback = |_: u16, _: &OdaGroup| unknown += 1;
+    {
+        let mut decoder = Decoder::default();
+        decoder.set_unknown_oda_callback(&mut callback);
+
+        let metadata = decoder.decode(&Message::new(
+            None,
+            Some(0x3018),
+            Some(0x0000),
+            Some(0xABCD),
+        ));
+        assert_eq!(metadata.oda.len(), 1);
+
+        decoder.reset();
+        let metadata = decoder.decode(&Message::new(
+            None,
+            Some(0xC000),
+            Some(0x1111),
+            Some(0x2222),
+        ));
+        assert!(metadata.oda.is_empty());
+    }
+    assert_eq!(unknown, 0);
+}
+
+/// Verifies that:
+///   - TMC single-group messages in group 8A are reported as events.
+///   - Immediate repetitions of a TMC message are reported once.
+#[test]
+fn tmc_single_group_message() {
+    let mut decoder = Decoder::default();
+    // Group 3A: 8A carries TMC (AID 0xCD46)
+    decoder.decode(&Message::new(
+        None,
+        Some(0x3010),
+        Some(0x0000),
+        Some(0xCD46),
+    ));
+    assert_eq!(decoder.poll_event(), None);
+
+    let message = Message::new(None, Some(0x800A), Some(0xD865), Some(0x3039));
+    decoder.decode(&message);
+    decoder.decode(&message);
+
+    assert_eq!(
+        decoder.poll_event(),
+        Some(Event::TmcMessage(TmcMessage {
+            event: 101,
+            location: 12345,
+            direction: TmcDirection::Negative,
+            extent: 3,
+            duration: 2,
+            diversion: true,
+            labels: Default::default(),
+        }))
+    );
+    assert_eq!(decoder.poll_event(), None);
+}
+
+/// Verifies that:
+///   - TMC announced with the AID 0xCD47 is decoded on the group assigned in group 3A.
+#[test]
+fn tmc_alternative_aid() {
+    let mut decoder = Decoder::default();
+    // Group 3A: 11A carries TMC (AID 0xCD47)
+    let metadata = decoder.decode(&Message::new(
+        None,
+        Some(0x3016),
+        Some(0x0000),
+        Some(0xCD47),
+    ));
+    assert_eq!(metadata.oda[0].aid(), 0xCD47);
+    assert!(metadata.tmc.is_some());
+
+    decoder.decode(&Message::new(
+        None,
+        Some(0xB00A),
+        Some(0xD865),
+        Some(0x3039),
+    ));
+    let Some(Event::TmcMessage(message)) = decoder.poll_event() else {
+        panic!("Expected a TMC message");
+    };
+    assert_eq!(message.event, 101);
+    assert_eq!(message.location, 12345);
+}
+
+/// Verifies that:
+///   - The TMC system information in group 3A is exposed in the metadata.
+#[test]
+fn tmc_service() {
+    let mut decoder = Decoder::default();
+    // Group 3A: 8A carries TMC, LTN 12, national scope
+    let metadata = decoder.decode(&Message::new(
+        None,
+        Some(0x3010),
+        Some((12 << 6) | 0x4),
+        Some(0xCD46),
+    ));
+    let service = metadata.tmc.unwrap();
+    assert_eq!(service.location_table, 12);
+    assert!(service.scope.national);
+}
+
+/// Verifies that:
+///   - An LTEF location table is loaded from the sample table.
+///   - Location codes, direction and extent resolve into named points, segments and roads.
+#[cfg(feature = "std")]
+#[test]
+fn tmc_location_table() {
+    use wrds::{TmcLocationKind, TmcLocationTable};
+
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/ltef");
+    let table = TmcLocationTable::load(dir).unwrap();
+    assert_eq!(table.table_number(), Some(1));
+    assert_eq!(table.len(), 7);
+
+    let chain = table.resolve(301, TmcDirection::Positive, 2).unwrap();
+    assert!(chain.complete);
+    let names: Vec<_> = chain
+        .locations
+        .iter()
+        .map(|location| location.first_name.as_deref().unwrap())
+        .collect();
+    assert_eq!(names, ["Midfield", "Airport", "Southbay-East"]);
+    let segments: Vec<_> = chain.segments.iter().map(|segment| segment.code).collect();
+    assert_eq!(segments, [200, 201]);
+    let road = chain.road.unwrap();
+    assert_eq!(road.kind, TmcLocationKind::Road);
+    assert_eq!(road.road_number.as_deref(), Some("A1"));
+    assert_eq!(road.road_name.as_deref(), Some("Coast Motorway"));
+    assert_eq!(road.first_name.as_deref(), Some("Northport"));
+
+    // The chain ends at the start of the road
+    let chain = table.resolve(301, TmcDirection::Negative, 3).unwrap();
+    assert!(!chain.complete);
+    assert_eq!(chain.secondary().code, 300);
+
+    assert_eq!(table.resolve(999, TmcDirection::Positive, 1), None);
+}
+
+/// Verifies that:
+///   - Group 9A is decoded into an emergency warning event with the channel ID from group 1A.
+///   - The emergency warning callback receives the same warning.
+#[test]
+fn emergency_warning() {
+    let mut received = Vec::new();
+    let mut callback = |warning: &EmergencyWarning| received.push(*warning);
+    let event = {
+        let mut decoder = Decoder::default();
+        decoder.set_emergency_warning_callback(&mut callback);
+
+        // Group 1A, slow labelling variant 7: EWS channel 0x0AB
+        decoder.decode(&Message::new(
+            None,
+            Some(0x1000),
+            Some(0x70AB),
+            Some(0x0000),
+        ));
+        // Group 9A
+        decoder.decode(&Message::new(
+            None,
+            Some(0x9011),
+            Some(0x2233),
+            Some(0x4455),
+        ));
+        decoder.poll_event()
+    };
+
+    let expected = EmergencyWarning {
+        channel_id: Some(0x0AB),
+        message: 0x11_2233_4455,
+    };
+    assert_eq!(event, Some(Event::EmergencyWarning(expected)));
+    assert_eq!(received, [expected]);
+}
+
+/// Verifies that:
+///   - An alarm (PTY 31) starts after two groups, before the PTY filter settles.
+///   - The alarm ends once PTY 31 is no longer transmitted.
+///   - The traffic announcement flag is decoded from group 0A.
+#[test]
+fn alarm_and_traffic_announcement() {
+    let mut decoder = Decoder::default();
+    // Group 0A with TP, TA and PTY 31
+    let alarm = Message::new(None, Some(0x07F0), None, Some(0x2020));
+    // Group 0A with TP, TA and PTY 1
+    let news = Message::new(None, Some(0x0430), None, Some(0x2020));
+
+    let metadata = decoder.decode(&alarm);
+    assert!(!metadata.alarm);
+    let metadata = decoder.decode(&alarm);
+    assert!(metadata.alarm);
+    assert!(metadata.is_announcement());
+    assert_eq!(metadata.pty, None);
+    assert_eq!(decoder.poll_event(), Some(Event::AlarmStarted));
+
+    for _ in 0..4 {
+        decoder.decode(&alarm);
+    }
+    let mut metadata = decoder.decode(&news);
+    assert!(metadata.alarm);
+    for _ in 0..3 {
+        metadata = decoder.decode(&news);
+    }
+    assert!(!metadata.alarm);
+    // The filtered PTY has not settled on the new programme type yet
+    assert_eq!(metadata.pty, None);
+    assert_eq!(metadata.ta, Some(TrafficAnnouncement(true)));
+    assert!(metadata.is_announcement());
+    assert_eq!(decoder.poll_event(), Some(Event::AlarmEnded));
+    assert_eq!(decoder.poll_event(), None);
+}
+
+/// Verifies that:
+///   - The paging codes, operator code and ECC are decoded from group 1A.
+#[test]
+fn radio_paging_codes() {
+    let mut decoder = Decoder::default();
+    // Group 1A: network group 1, battery saving interval 1, OPC 3, ECC 0xE0
+    let metadata = decoder.decode(&Message::new(
+        None,
+        Some(0x1005),
+        Some(0x03E0),
+        Some(0x0000),
+    ));
+    assert_eq!(metadata.ecc, Some(0xE0));
+    let paging = metadata.paging.unwrap();
+    assert_eq!(paging.network_group, 1);
+    assert_eq!(paging.battery_saving_interval, 1);
+    assert_eq!(paging.operator_code, Some(3));
+    assert_eq!(paging.area_code, None);
+}
+
+/// Verifies that:
+///   - A paging message in group 7A is reassembled and reported as an event.
+#[cfg(feature = "unstable")]
+#[test]
+fn radio_paging_message() {
+    let mut decoder = Decoder::default();
+    // Group 7A: pager 12-3456, numeric message in one group
+    decoder.decode(&Message::new(
+        None,
+        Some(0x7000),
+        Some(0x1234),
+        Some(0x5611),
+    ));
+    decoder.decode(&Message::new(
+        None,
+        Some(0x7001),
+        Some(0x0800),
+        Some(0x555F),
+    ));
+    assert_eq!(
+        decoder.poll_event(),
+        Some(Event::Paging(PagingMessage {
+            pager: PagerAddress {
+                group: 12,
+                individual: 3456,
+            },
+            content: PagingContent::Numeric("0800555".try_into().unwrap()),
+            network: None,
+        }))
+    );
+}
+
+/// Verifies that:
+///   - Enhanced paging information is decoded from groups 1A and 13A.
+///   - Paging messages carry the international network once the ECC, PI and paging
+///     identification are known.
+#[cfg(feature = "unstable")]
+#[test]
+fn enhanced_paging() {
+    let mut decoder = Decoder::default();
+    for _ in 0..5 {
+        // Group 1A, slow labelling variant 0: ECC 0xE0
+        decoder.decode(&Message::new(
+            Some(0xD301),
+            Some(0x1000),
+            Some(0x00E0),
+            Some(0x0000),
+        ));
+    }
+    // Group 1A, slow labelling variant 2: OPC 5, PAC 42
+    decoder.decode(&Message::new(
+        Some(0xD301),
+        Some(0x1000),
+        Some(0x252A),
+        Some(0x0000),
+    ));
+    // Group 13A: cycle 1, interval 4, batch 2
+    let metadata = decoder.decode(&Message::new(
+        Some(0xD301),
+        Some(0xD001),
+        Some(0x4200),
+        Some(0x0000),
+    ));
+    let paging = metadata.paging.unwrap();
+    assert_eq!(
+        paging.enhanced,
+        Some(EnhancedPaging {
+            cycle: 1,
+            interval: 4,
+            batch: 2,
+        })
+    );
+
+    // Group 7A: beep for pager 00-0001
+    decoder.decode(&Message::new(
+        Some(0xD301),
+        Some(0x7000),
+        Some(0x0000),
+        Some(0x0100),
+    ));
+    let Some(Event::Paging(message)) = decoder.poll_event() else {
+        panic!("Expected a paging event");
+    };
+    assert_eq!(
+        message.network,
+        Some(PagingNetwork {
+            ecc: 0xE0,
+            country_code: 0xD,
+            operator_code: 5,
+            area_code: 42,
+        })
+    );
+}
+
+/// Verifies that:
+///   - Group 5A is demultiplexed into per-channel TDC byte streams.
+///   - Group 5A is not decoded as TDC once an ODA is assigned to it.
+#[test]
+fn transparent_data_channel() {
+    let mut decoder = Decoder::default();
+    let mut buffer = [0; 8];
+
+    // Group 5A, channel 2
+    decoder.decode(&Message::new(
+        None,
+        Some(0x5002),
+        Some(0x5244),
+        Some(0x5321),
+    ));
+    assert_eq!(decoder.tdc_available(2), 4);
+    assert_eq!(decoder.read_tdc(2, &mut buffer), 4);
+    assert_eq!(&buffer[..4], b"RDS!");
+
+    // Group 3A: 5A carries AID 0xABCD
+    decoder.decode(&Message::new(
+        None,
+        Some(0x300A),
+        Some(0x0000),
+        Some(0xABCD),
+    ));
+    decoder.decode(&Message::new(
+        None,
+        Some(0x5002),
+        Some(0x5244),
+        Some(0x5321),
+    ));
+    assert_eq!(decoder.tdc_available(2), 0);
+}
+
+/// Verifies that:
+///   - Groups 6A and 6B are delivered as in-house data events with the PI of the group.
+///   - With an in-house callback, the payloads only go to the callback and no events are queued.
+#[test]
+fn in_house_data() {
+    let groups = [
+        // Group 6A
+        Message::new(Some(0xC201), Some(0x6013), Some(0x1122), Some(0x3344)),
+        // Group 6B
+        Message::new(Some(0xC202), Some(0x6807), Some(0xC202), Some(0x5566)),
+    ];
+    let expected = [
+        InHouseData {
+            pi: Some(ProgrammeIdentifier(0xC201)),
+            variant: GroupVariant::A,
+            block2_data: 0x13,
+            block3: Some(0x1122),
+            block4: 0x3344,
+        },
+        InHouseData {
+            pi: Some(ProgrammeIdentifier(0xC202)),
+            variant: GroupVariant::B,
+            block2_data: 0x07,
+            block3: None,
+            block4: 0x5566,
+        },
+    ];
+
+    let mut decoder = Decoder::default();
+    for group in &groups {
+        decoder.decode(group);
+    }
+    let events = [decoder.poll_event(), decoder.poll_event()];
+    assert_eq!(events, expected.map(|data| Some(Event::InHouse(data))));
+
+    let mut received = Vec::new();
+    let mut callback = |data: &InHouseData| received.push(*data);
+    let event = {
+        let mut decoder = Decoder::default();
+        decoder.set_in_house_callback(&mut callback);
+        for group in &groups {
+            decoder.decode(group);
+        }
+        decoder.poll_event()
+    };
+    assert_eq!(event, None);
+    assert_eq!(received, expected);
+}
+
+/// Verifies that:
+///   - The DAB cross-referencing ODA is decoded into DAB linkages in the metadata.
+///   - Reset clears the DAB linkages.
+#[test]
+fn dab_cross_referencing() {
+    let mut decoder = Decoder::default();
+
+    // Group 3A: 12A carries AID 0x0093
+    decoder.decode(&Message::new(
+        None,
+        Some(0x3018),
+        Some(0x0000),
+        Some(0x0093),
+    ));
+    // Group 12A, ensemble table: EId 0xE101, mode I, 225.648 MHz
+    decoder.decode(&Message::new(
+        None,
+        Some(0xC004),
+        Some(0x3717),
+        Some(0xE101),
+    ));
+    // Group 12A, service table variant 0: SId 0xD210 in EId 0xE101
+    let metadata = decoder.decode(&Message::new(
+        None,
+        Some(0xC010),
+        Some(0xE101),
+        Some(0xD210),
+    ));
+
+    let mut expected = DabLinkage::new(0xE101);
+    expected.frequency = Some(225_648);
+    expected.mode = DabMode::ModeI;
+    expected.service_ids.push(0xD210).unwrap();
+    assert_eq!(metadata.dab, [expected]);
+
+    decoder.reset();
+    assert!(decoder
+        .decode(&Message::new(None, None, None, None))
+        .dab
+        .is_empty());
+}
+
+/// Verifies that:
+///   - The LA bit of group 1A and the linkage information of group 14A variant 12 are decoded.
+///   - Networks in the same linkage set as the tuned network are reported as linked.
+#[test]
+fn programme_linkage() {
+    const TUNED_PI: u16 = 0xD301;
+    let mut decoder = Decoder::default();
+
+    // Group 1A with LA, slow labelling variant 0
+    decoder.decode(&Message::new(
+        Some(TUNED_PI),
+        Some(0x1000),
+        Some(0x80E0),
+        Some(0x0000),
+    ));
+    // Group 14A variant 12 for the tuned network and two other networks
+    for (info, pi) in [(0x0042, TUNED_PI), (0x8042, 0xD302), (0x8043, 0xD303)] {
+        decoder.decode(&Message::new(
+            Some(TUNED_PI),
+            Some(0xE00C),
+            Some(info),
+            Some(pi),
+        ));
+    }
+    // Settle the PI filter
+    for _ in 0..5 {
+        decoder.decode(&Message::new(Some(TUNED_PI), None, None, None));
+    }
+    let metadata = decoder.decode(&Message::new(Some(TUNED_PI), None, None, None));
+
+    assert!(metadata.linkage_actuator);
+    assert_eq!(metadata.linkage.len(), 3);
+    assert_eq!(
+        metadata.linkage[0],
+        NetworkLinkage {
+            pi: ProgrammeIdentifier(TUNED_PI),
+            info: LinkageInfo {
+                linkage_actuator: false,
+                extended_generic: false,
+                international: false,
+                linkage_set_number: 0x042,
+            },
+        }
+    );
+    assert_eq!(decoder.linked_programmes(), [ProgrammeIdentifier(0xD302)]);
+}
+
+/// Verifies that:
+///   - A station logo is assembled from a generated RFT group stream on streams 0 and 1.
+///     The groups were generated with the layouts of `src/rft.rs` and are not a recording.
+///   - The segment corrupted in the first cycle is received again after CRC verification.
+#[cfg(all(feature = "std", feature = "unstable"))]
+#[test]
+fn rft_station_logo_from_generated_groups() {
+    use wrds::RftDecoder;
+
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/rft");
+    let groups = std::fs::read_to_string(format!("{dir}/logo.groups")).unwrap();
+    let logo = std::fs::read(format!("{dir}/logo.png")).unwrap();
+
+    let mut rft = RftDecoder::new();
+    {
+        let mut decoder = Decoder::default();
+        decoder.register_oda_handler(&mut rft).unwrap();
+        for line in groups
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let values: Vec<u16> = line
+                .split_whitespace()
+                .map(|value| u16::from_str_radix(value, 16).unwrap())
+                .collect();
+            let [stream, block1, block2, block3, block4] = values[..] else {
+                panic!("Invalid group: {line}");
+            };
+            decoder.decode(&Message::with_stream(
+                stream as u8,
+                Some(block1),
+                Some(block2),
+                Some(block3),
+                Some(block4),
+            ));
+        }
+    }
+
+    let file = rft.poll_file().unwrap();
+    assert_eq!(file.pipe, 1);
+    assert_eq!(file.file_id, 7);
+    assert_eq!(file.version, 2);
+    assert!(file.crc_verified);
+    assert_eq!(file.mime_type(), Some("image/png"));
+    assert_eq!(file.data, logo);
+    assert_eq!(rft.poll_file(), None);
+}
+
+/// Verifies that:
+///   - Type C groups of the RDS2 streams are passed to the handler of the AID assigned
+///     to their channel.
+///   - Block 1 of these groups does not affect the PI.
+#[cfg(feature = "unstable")]
+#[test]
+fn rds2_type_c_groups() {
+    #[derive(Default)]
+    struct Handler {
+        groups: Vec<TypeCGroup>,
+    }
+
+    impl OdaHandler for Handler {
+        fn aid(&self) -> u16 {
+            0x1234
+        }
+
+        fn decode_group(&mut self, _: &OdaGroup) {}
+
+        fn decode_type_c_group(&mut self, group: &TypeCGroup) {
+            self.groups.push(*group);
+        }
+    }
+
+    const PI: u16 = 0xC201;
+    let data = Message::with_stream(2, Some(0x0501), Some(0x0203), Some(0x0405), Some(0x0607));
+    let mut handler = Handler::default();
+    let metadata = {
+        let mut decoder = Decoder::default();
+        decoder.register_oda_handler(&mut handler).unwrap();
+        // Channel 5 is not assigned yet
+        decoder.decode(&data);
+        // Channel 0: AID 0x1234 on channel 5
+        decoder.decode(&Message::with_stream(
+            1,
+            Some(0x0012),
+            Some(0x3405),
+            Some(0x0000),
+            Some(0x0000),
+        ));
+        for _ in 0..10 {
+            decoder.decode(&Message::new(Some(PI), None, None, None));
+            decoder.decode(&data);
+        }
+        // Incomplete groups and unknown streams are dropped
+        decoder.decode(&Message::with_stream(1, Some(0x0501), None, None, None));
+        decoder.decode(&Message { stream: 4, ..data })
+    };
+
+    assert_eq!(metadata.pi, Some(ProgrammeIdentifier(PI)));
+    assert_eq!(handler.groups.len(), 10);
+    assert_eq!(
+        handler.groups[0],
+        TypeCGroup {
+            stream: 2,
+            function_header: FunctionHeader::Oda { channel: 5 },
+            data: [1, 2, 3, 4, 5, 6, 7],
+        }
+    );
+}
+
+/// Verifies that:
+///   - Groups of the RDS2 streams do not affect the PI or any other metadata.
+#[test]
+fn rds2_streams_do_not_affect_metadata() {
+    const PI: u16 = 0xC201;
+    // Block 2 would be a group 0A on stream 0
+    let stream1 = Message::with_stream(1, Some(0x0012), Some(0x0000), Some(0x0000), Some(0x4142));
+    let mut decoder = Decoder::default();
+    let mut metadata = Metadata::default();
+    for _ in 0..10 {
+        decoder.decode(&Message::new(Some(PI), None, None, None));
+        metadata = decoder.decode(&stream1);
+    }
+
+    assert_eq!(metadata.pi, Some(ProgrammeIdentifier(PI)));
+    assert_eq!(metadata.ps, None);
+}
+
+/// Verifies that:
+///   - The code table switching sequences are honoured in the PS and the PTYN.
+///   - The code table active at the end of the text is reported.
+#[test]
+fn code_table_switching() {
+    const PI: u16 = 0x1201;
+    let mut decoder = Decoder::default();
+
+    // Group 0A: SO SO then "RADIO " in the lower half of the G1 code table
+    for (idx, chars) in [0x0E0E, 0x5241, 0x4449, 0x4F20].into_iter().enumerate() {
+        decoder.decode(&Message::new(
+            Some(PI),
+            Some(idx as u16),
+            Some(0x0000),
+            Some(chars),
+        ));
+    }
+    // Group 10A: ESC n then "POP" in the lower half of the G2 code table
+    decoder.decode(&Message::new(
+        Some(PI),
+        Some(0xA000),
+        Some(0x1B6E),
+        Some(0x504F),
+    ));
+    decoder.decode(&Message::new(
+        Some(PI),
+        Some(0xA001),
+        Some(0x5020),
+        Some(0x2020),
+    ));
+    for _ in 0..10 {
+        decoder.decode(&Message::new(Some(PI), None, None, None));
+    }
+    let metadata = decoder.decode(&Message::new(Some(PI), None, None, None));
+
+    let ps = metadata.ps.unwrap();
+    assert_eq!(ps.as_str(), "RADIO ");
+    assert_eq!(ps.code_table(), RdsCodeTable::G1);
+    let ptyn = metadata.ptyn.unwrap();
+    assert_eq!(ptyn.as_str(), "POP   ");
+    assert_eq!(ptyn.code_table(), RdsCodeTable::G2);
+}
+
+/// Verifies that:
+///   - A PS encoded with the charset encoder is decoded back through group 0A.
+#[test]
+fn charset_encoder_round_trip() {
+    const PI: u16 = 0x7201;
+    let mut ps = [b' '; 8];
+    for (dst, byte) in ps.iter_mut().zip(RdsCharsetEncoder::new().encode("Zürich")) {
+        *dst = byte;
+    }
+
+    let mut decoder = Decoder::default();
+    for (idx, chars) in ps.chunks(2).enumerate() {
+        decoder.decode(&Message::new(
+            Some(PI),
+            Some(idx as u16),
+            Some(0x0000),
+            Some(u16::from_be_bytes([chars[0], chars[1]])),
+        ));
+    }
+    for _ in 0..10 {
+        decoder.decode(&Message::new(Some(PI), None, None, None));
+    }
+    let ps = decoder
+        .decode(&Message::new(Some(PI), None, None, None))
+        .ps
+        .unwrap();
+    assert_eq!(ps.as_str(), "Zürich  ");
+    assert_eq!(ps.code_table(), RdsCodeTable::G0);
+}
+
+/// Verifies that:
+///   - With text voting, the PS is only confirmed after repeated agreement.
+///   - A corrupted PS segment does not replace the confirmed PS.
+#[test]
+fn ps_text_voting() {
+    const PI: u16 = 0x6201;
+    let mut decoder = Decoder::default();
+    assert!(decoder.set_text_voting(Some(0)).is_err());
+    assert!(decoder.set_text_voting(Some(MAX_TEXT_VOTES + 1)).is_err());
+    decoder.set_text_voting(Some(2)).unwrap();
+
+    let ps_group = |idx: u16, chars: &[u8; 2]| {
+        Message::new(
+            Some(PI),
+            Some(idx),
+            Some(0x0000),
+            Some(u16::from_be_bytes(*chars)),
+        )
+    };
+    for _ in 0..10 {
+        decoder.decode(&Message::new(Some(PI), None, None, None));
+    }
+    for (idx, chars) in [b"RA", b"DI", b"O ", b"1 "].into_iter().enumerate() {
+        decoder.decode(&ps_group(idx as u16, chars));
+    }
+    assert_eq!(
+        decoder.decode(&Message::new(Some(PI), None, None, None)).ps,
+        None
+    );
+
+    for (idx, chars) in [b"RA", b"DI", b"O ", b"1 "].into_iter().enumerate() {
+        decoder.decode(&ps_group(idx as u16, chars));
+    }
+    let metadata = decoder.decode(&ps_group(1, b"DX"));
+    assert_eq!(metadata.ps.unwrap().as_str(), "RADIO 1 ");
+}